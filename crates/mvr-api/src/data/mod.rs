@@ -9,6 +9,7 @@ pub(crate) mod package_analytics;
 pub(crate) mod package_by_name_loader;
 pub(crate) mod package_dependencies;
 pub(crate) mod package_dependents;
+pub(crate) mod package_modules;
 pub(crate) mod package_resolver;
 pub(crate) mod reader;
 pub(crate) mod resolution_loader;
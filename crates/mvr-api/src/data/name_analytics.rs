@@ -14,6 +14,8 @@ use crate::errors::ApiError;
 
 use super::reader::Reader;
 
+pub use mvr_types::api::AnalyticsValue;
+
 /// The `NameAnalyticsKey` expects a "Name" (MVR name),
 /// and an `ObjectId`, which can be ANY version of the package
 /// the name resolves to. This can be retrieved from the `package_by_name_loader`.
@@ -53,15 +55,6 @@ pub struct DependenciesCountPerPackageQueryResponse {
     pub count: i64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
-pub struct AnalyticsValue {
-    pub date_from: NaiveDate,
-    pub date_to: NaiveDate,
-    pub direct: i64,
-    pub propagated: i64,
-    pub total: i64,
-}
-
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct AnalyticsAggregatedValues {
     pub analytics: Vec<AnalyticsValue>,
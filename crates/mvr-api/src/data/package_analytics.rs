@@ -3,42 +3,22 @@ use std::collections::HashMap;
 use async_graphql::dataloader::Loader;
 use chrono::NaiveDate;
 use diesel::{
-    prelude::Queryable,
     query_dsl::methods::{FilterDsl, OrderDsl, SelectDsl},
     ExpressionMethods,
 };
-use serde::{Deserialize, Serialize};
 use sui_sdk_types::Address;
 
 use crate::errors::ApiError;
 
 use super::reader::Reader;
 
+pub use mvr_types::api::PackageAnalytics;
+
 /// The `NaiveDate` is the date of the analytics query, as we are caching the query on a daily basis
 /// per instance.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct PackageAnalyticsKey(pub Address, pub NaiveDate);
 
-#[derive(Serialize, Deserialize, Clone, Queryable)]
-pub struct PackageAnalytics {
-    #[diesel(sql_type = diesel::sql_types::VarChar)]
-    pub package_id: String,
-    #[diesel(sql_type = diesel::sql_types::Date)]
-    pub call_date: NaiveDate,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub direct_calls: i64,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub propagated_calls: i64,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub total_calls: i64,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub aggregated_direct_calls: i64,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub aggregated_propagated_calls: i64,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub aggregated_total_calls: i64,
-}
-
 #[async_trait::async_trait]
 impl Loader<PackageAnalyticsKey> for Reader {
     type Value = Vec<PackageAnalytics>;
@@ -10,7 +10,6 @@ use diesel::{
     sql_types::{Array, BigInt, Text},
 };
 use mvr_types::name::VersionedName;
-use serde::{Deserialize, Serialize};
 
 use crate::errors::ApiError;
 
@@ -40,28 +39,7 @@ pub struct PackageByName {
     pub git_info_tag: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct PackageByNameBaseData {
-    pub name: String,
-    pub metadata: serde_json::Value,
-    pub package_info: Option<PackageInfoResponse>,
-    pub git_info: Option<GitInfo>,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct PackageInfoResponse {
-    pub id: String,
-    pub git_table_id: String,
-    pub default_name: Option<String>,
-    pub metadata: serde_json::Value,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct GitInfo {
-    pub repository_url: String,
-    pub path: String,
-    pub tag: String,
-}
+pub use mvr_types::api::{GitInfo, PackageByNameBaseData, PackageInfoResponse};
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct PackageByNameKey(pub VersionedName);
@@ -3,31 +3,19 @@ use std::{collections::HashMap, str::FromStr};
 use async_graphql::dataloader::Loader;
 use diesel::{ExpressionMethods, QueryDsl};
 use mvr_schema::schema::package_dependencies;
-use serde::{Deserialize, Serialize};
 use sui_sdk_types::Address;
 
 use crate::errors::ApiError;
 
 use super::reader::Reader;
 
+pub use mvr_types::api::PackageDependencies;
+
 // This is a key to load all dependencies for a package.
 // Since there are system limits, we do not paginate this endpoint.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct PackageDependenciesKey(pub Address);
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct PackageDependencies {
-    pub dependencies: Vec<Address>,
-}
-
-impl Default for PackageDependencies {
-    fn default() -> Self {
-        Self {
-            dependencies: Vec::new(),
-        }
-    }
-}
-
 #[async_trait::async_trait]
 impl Loader<PackageDependenciesKey> for Reader {
     type Value = PackageDependencies;
@@ -4,7 +4,6 @@ use async_graphql::dataloader::Loader;
 use chrono::NaiveDate;
 use diesel::{
     dsl::count_star,
-    prelude::QueryableByName,
     query_dsl::methods::{FilterDsl, SelectDsl},
     sql_types::{BigInt, Integer, Text},
     ExpressionMethods,
@@ -18,6 +17,8 @@ use crate::{errors::ApiError, utils::pagination::PaginationLimit};
 
 use super::reader::Reader;
 
+pub use mvr_types::api::PackageDependent;
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct PackageDependentsCursor {
     pub package_id: Option<Address>,
@@ -35,18 +36,6 @@ pub struct PackageDependentsKey(
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct PackageDependentsCountKey(pub Address, pub NaiveDate);
 
-#[derive(Serialize, Deserialize, Clone, QueryableByName)]
-pub struct PackageDependent {
-    #[diesel(sql_type = diesel::sql_types::Text)]
-    pub package_id: String,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub aggregated_total_calls: i64,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub aggregated_direct_calls: i64,
-    #[diesel(sql_type = diesel::sql_types::BigInt)]
-    pub aggregated_propagated_calls: i64,
-}
-
 #[async_trait::async_trait]
 impl Loader<PackageDependentsKey> for Reader {
     type Value = Vec<PackageDependent>;
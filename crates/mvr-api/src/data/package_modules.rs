@@ -0,0 +1,77 @@
+use std::{collections::HashMap, str::FromStr};
+
+use async_graphql::dataloader::Loader;
+use base64::{engine::general_purpose, Engine};
+use diesel::{ExpressionMethods, QueryDsl};
+use mvr_schema::schema::packages;
+use sui_sdk_types::Address;
+use sui_types::move_package::MovePackage;
+
+use crate::errors::ApiError;
+
+use super::reader::Reader;
+
+pub use mvr_types::api::PackageModules;
+
+// This is a key to load the bytecode of all modules of a package.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PackageModulesKey(pub Address);
+
+#[async_trait::async_trait]
+impl Loader<PackageModulesKey> for Reader {
+    type Value = PackageModules;
+    type Error = ApiError;
+
+    async fn load(
+        &self,
+        keys: &[PackageModulesKey],
+    ) -> Result<HashMap<PackageModulesKey, Self::Value>, Self::Error> {
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut connection = self.connect().await?;
+
+        let package_ids = keys.iter().map(|k| k.0.to_string()).collect::<Vec<_>>();
+
+        let query = packages::table
+            .select((
+                packages::package_id,
+                packages::original_id,
+                packages::package_version,
+                packages::move_package,
+            ))
+            .filter(packages::package_id.eq_any(package_ids));
+
+        let result: Vec<(String, String, i64, Vec<u8>)> = connection.results(query).await?;
+
+        let mut packages = HashMap::new();
+
+        for (package_id, original_id, version, move_package) in result {
+            let move_package: MovePackage = bcs::from_bytes(&move_package).map_err(|e| {
+                ApiError::InternalServerError(format!("Failed to deserialize package: {e}"))
+            })?;
+
+            // SAFETY: We know that the package_id and original_id are valid addresses
+            let package_id = Address::from_str(&package_id).unwrap();
+
+            packages.insert(
+                PackageModulesKey(package_id),
+                PackageModules {
+                    package_id,
+                    original_id: Address::from_str(&original_id).unwrap(),
+                    version: version as u64,
+                    modules: move_package
+                        .serialized_module_map()
+                        .iter()
+                        .map(|(name, bytes)| {
+                            (name.clone(), general_purpose::STANDARD.encode(bytes))
+                        })
+                        .collect(),
+                },
+            );
+        }
+
+        Ok(packages)
+    }
+}
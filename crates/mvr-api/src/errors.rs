@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use mvr_types::errors::{MoveRegistryError, NameServiceError};
-use serde::Serialize;
+use mvr_types::{
+    api::ErrorResponse,
+    errors::{MoveRegistryError, NameServiceError},
+};
 
 use axum::{
     http::StatusCode,
@@ -31,11 +33,6 @@ pub enum ApiError {
     BatchSizeLimitExceeded(usize, usize),
 }
 
-#[derive(Serialize)]
-struct ErrorResponse {
-    message: String,
-}
-
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
@@ -4,6 +4,7 @@
 use std::{collections::HashMap, sync::Arc};
 
 use axum::{extract::State, http::StatusCode};
+use mvr_types::api::MAX_BATCH_SIZE;
 use sui_sdk_types::Address;
 
 use crate::{
@@ -11,7 +12,6 @@ use crate::{
     errors::ApiError,
 };
 
-const BATCH_SIZE_DEFAULT: usize = 50;
 const MAX_SEARCH_QUERY_LENGTH: usize = 255;
 
 pub(crate) mod names;
@@ -40,7 +40,7 @@ fn into_object_id_map(resolution: &HashMap<String, ResolutionData>) -> HashMap<S
 }
 
 fn validate_batch_size<T>(items: &[T], limit: Option<usize>) -> Result<(), ApiError> {
-    let limit = limit.unwrap_or(BATCH_SIZE_DEFAULT);
+    let limit = limit.unwrap_or(MAX_BATCH_SIZE);
 
     if items.len() > limit {
         Err(ApiError::BatchSizeLimitExceeded(items.len(), limit))
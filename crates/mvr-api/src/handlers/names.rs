@@ -5,68 +5,36 @@ use axum::{
     Json,
 };
 use chrono::Local;
-use diesel::{
-    prelude::QueryableByName,
-    sql_types::{Integer, VarChar},
-};
+use diesel::sql_types::{Integer, VarChar};
 use futures::try_join;
-use mvr_types::name::VersionedName;
+use mvr_types::{
+    api::{
+        BulkNamesRequest, BulkNamesResponse, NameAnalyticsResponse, NameSearchQueryParams,
+        NameSearchResponse, PackageByNameResponse,
+    },
+    name::VersionedName,
+    name_service::{Domain, DomainFormat},
+};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use sui_sdk_types::Address;
 
 use crate::{
     data::{
         app_state::AppState,
-        name_analytics::{AnalyticsValue, NameAnalyticsKey, NameDependentsCountKey},
-        package_by_name_loader::{PackageByNameBaseData, PackageByNameKey},
+        name_analytics::{NameAnalyticsKey, NameDependentsCountKey},
+        package_by_name_loader::PackageByNameKey,
         resolution_loader::ResolutionKey,
     },
     errors::ApiError,
     utils::pagination::{format_paginated_response, Cursor, PaginatedResponse, PaginationLimit},
 };
 
-use super::validate_search_query;
-
-#[derive(Serialize, Deserialize)]
-pub struct PackageByNameResponse {
-    #[serde(flatten)]
-    pub package_by_name_data: PackageByNameBaseData,
-    pub version: i64,
-    pub package_address: Address,
-}
-
-#[derive(Serialize, Deserialize, QueryableByName)]
-pub struct NameSearchResponse {
-    #[diesel(sql_type = diesel::sql_types::Text)]
-    pub name: String,
-    #[diesel(sql_type = diesel::sql_types::Jsonb)]
-    pub metadata: Value,
-    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub mainnet_package_info_id: Option<String>,
-    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
-    pub testnet_package_info_id: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct NameSearchQueryParams {
-    pub search: Option<String>,
-    pub cursor: Option<String>,
-    pub limit: Option<u32>,
-    pub is_linked: Option<bool>,
-}
+use super::{validate_batch_size, validate_search_query};
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct NameCursor {
     pub name: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Default)]
-pub struct AnalyticsResponse {
-    pub analytics: Vec<AnalyticsValue>,
-    pub total_dependents: i64,
-}
-
 pub struct Names;
 
 impl Names {
@@ -104,6 +72,68 @@ impl Names {
         }
     }
 
+    /// Returns the package data (same as `get_by_name`) for multiple names at once.
+    pub async fn bulk_get_by_name(
+        State(app_state): State<Arc<AppState>>,
+        Json(payload): Json<BulkNamesRequest>,
+    ) -> Result<Json<BulkNamesResponse>, ApiError> {
+        validate_batch_size(&payload.names, None)?;
+
+        // Malformed names resolve to `null`, the same as names that do not exist,
+        // so a single bad entry does not fail the whole batch.
+        let (names, invalid): (Vec<_>, Vec<_>) = payload
+            .names
+            .iter()
+            .map(|name| (name, VersionedName::from_str(name)))
+            .partition(|(_, parsed)| parsed.is_ok());
+
+        let names = names
+            .into_iter()
+            .filter_map(|(_, parsed)| parsed.ok())
+            .collect::<Vec<_>>();
+
+        let resolutions = app_state
+            .loader()
+            .load_many(names.iter().cloned().map(ResolutionKey))
+            .await?;
+
+        // Same as in `get_by_name`, we force the version to the actual version of each package,
+        // so our query can fetch the `GitInfo` for the correct version.
+        let packages = app_state
+            .loader()
+            .load_many(resolutions.iter().map(|(key, address_data)| {
+                let mut versioned = key.0.clone();
+                versioned.version = Some(address_data.version as u64);
+                PackageByNameKey(versioned)
+            }))
+            .await?;
+
+        let names = names
+            .into_iter()
+            .map(|name| {
+                let Some(address_data) = resolutions.get(&ResolutionKey(name.clone())) else {
+                    return (name.to_string(), None);
+                };
+
+                let mut versioned = name.clone();
+                versioned.version = Some(address_data.version as u64);
+
+                let response = packages.get(&PackageByNameKey(versioned)).map(|package| {
+                    PackageByNameResponse {
+                        package_by_name_data: package.clone(),
+                        version: address_data.version,
+                        package_address: address_data.id,
+                    }
+                });
+
+                (name.to_string(), response)
+            })
+            .chain(invalid.into_iter().map(|(name, _)| (name.clone(), None)))
+            .collect();
+
+        Ok(Json(BulkNamesResponse { names }))
+    }
+
     pub async fn search_names(
         Query(params): Query<NameSearchQueryParams>,
         State(app_state): State<Arc<AppState>>,
@@ -114,17 +144,29 @@ impl Names {
         let limit = PaginationLimit::new(params.limit)?;
         let cursor = Cursor::decode_or_default::<NameCursor>(&params.cursor)?;
 
+        // Names are stored as `@org/app`, so an organization matches the part before the first `/`.
+        // It is compared with `=` (not `LIKE`), so `_` and `%` are never treated as wildcards.
+        let org = params
+            .org
+            .map(|org| {
+                Domain::from_str(&org)
+                    .map(|domain| domain.format(DomainFormat::At))
+                    .map_err(|_| ApiError::BadRequest(format!("Invalid organization: {}", org)))
+            })
+            .transpose()?;
+
         let mut connection = app_state.reader().connect().await?;
 
         let query = diesel::sql_query(
             format!("SELECT name, metadata, mainnet_id as mainnet_package_info_id, testnet_id as testnet_package_info_id, (CASE WHEN name SIMILAR TO $1 OR to_tsvector('english', metadata->>'description') @@ plainto_tsquery($2) THEN 1 ELSE 0 END) AS relevance 
-FROM name_records WHERE ( name SIMILAR TO $1 OR to_tsvector('english', metadata->>'description') @@ plainto_tsquery($2) ) AND name > $3 {} 
+FROM name_records WHERE ( name SIMILAR TO $1 OR to_tsvector('english', metadata->>'description') @@ plainto_tsquery($2) ) AND name > $3 AND ($5 = '' OR split_part(name, '/', 1) = $5) {} 
 ORDER BY relevance DESC, name ASC LIMIT $4", if params.is_linked.unwrap_or(false) { "AND (mainnet_id IS NOT NULL OR testnet_id IS NOT NULL) " } else { "" })
         )
         .bind::<VarChar, _>(format!("%{}%", search))
         .bind::<VarChar, _>(search)
         .bind::<VarChar, _>(cursor.name.unwrap_or_default())
-        .bind::<Integer, _>(limit.query_limit() as i32);
+        .bind::<Integer, _>(limit.query_limit() as i32)
+        .bind::<VarChar, _>(org.unwrap_or_default());
 
         let results: Vec<NameSearchResponse> = connection.results(query).await?;
 
@@ -142,7 +184,7 @@ ORDER BY relevance DESC, name ASC LIMIT $4", if params.is_linked.unwrap_or(false
     pub async fn get_analytics(
         Path(name): Path<String>,
         State(app_state): State<Arc<AppState>>,
-    ) -> Result<Json<AnalyticsResponse>, ApiError> {
+    ) -> Result<Json<NameAnalyticsResponse>, ApiError> {
         let name = VersionedName::from_str(&name)?;
 
         let Some(resolution) = app_state
@@ -166,7 +208,7 @@ ORDER BY relevance DESC, name ASC LIMIT $4", if params.is_linked.unwrap_or(false
             )),
         )?;
 
-        Ok(Json(AnalyticsResponse {
+        Ok(Json(NameAnalyticsResponse {
             analytics: analytics.unwrap_or_default().analytics,
             total_dependents: total_dependents.unwrap_or_default(),
         }))
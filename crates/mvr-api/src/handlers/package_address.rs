@@ -6,34 +6,24 @@ use axum::{
 };
 use chrono::Local;
 use futures::try_join;
-use serde::{Deserialize, Serialize};
+use mvr_types::api::{DependentsQueryParams, PackageAnalyticsResponse};
 use sui_sdk_types::Address;
 
 use crate::{
     data::{
         app_state::AppState,
-        package_analytics::{PackageAnalytics, PackageAnalyticsKey},
+        package_analytics::PackageAnalyticsKey,
         package_dependencies::{PackageDependencies, PackageDependenciesKey},
         package_dependents::{
             PackageDependent, PackageDependentsCountKey, PackageDependentsCursor,
             PackageDependentsKey,
         },
+        package_modules::{PackageModules, PackageModulesKey},
     },
     errors::ApiError,
     utils::pagination::{format_paginated_response, Cursor, PaginatedResponse, PaginationLimit},
 };
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct DependentsQueryParams {
-    pub cursor: Option<String>,
-    pub limit: Option<u32>,
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct AnalyticsResponse {
-    pub analytics: Vec<PackageAnalytics>,
-}
-
 pub struct PackageAddress;
 
 impl PackageAddress {
@@ -98,7 +88,7 @@ impl PackageAddress {
     pub async fn analytics(
         Path(package_address): Path<String>,
         State(app_state): State<Arc<AppState>>,
-    ) -> Result<Json<AnalyticsResponse>, ApiError> {
+    ) -> Result<Json<PackageAnalyticsResponse>, ApiError> {
         let object_id = Address::from_str(&package_address)
             .map_err(|e| ApiError::BadRequest(format!("Invalid package address: {}", e)))?;
 
@@ -107,8 +97,26 @@ impl PackageAddress {
             .load_one(PackageAnalyticsKey(object_id, Local::now().date_naive()))
             .await?;
 
-        Ok(Json(AnalyticsResponse {
+        Ok(Json(PackageAnalyticsResponse {
             analytics: analytics.unwrap_or_default(),
         }))
     }
+
+    /// Returns the bytecode of all modules of a package, as stored on-chain.
+    /// This is useful to verify that a package matches its source code.
+    pub async fn modules(
+        Path(package_address): Path<String>,
+        State(app_state): State<Arc<AppState>>,
+    ) -> Result<Json<PackageModules>, ApiError> {
+        let object_id = Address::from_str(&package_address)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid package address: {}", e)))?;
+
+        let modules = app_state
+            .loader()
+            .load_one(PackageModulesKey(object_id))
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("Package {} not found", object_id)))?;
+
+        Ok(Json(modules))
+    }
 }
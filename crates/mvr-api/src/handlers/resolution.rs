@@ -7,35 +7,22 @@ use axum::{
     extract::{Path, State},
     Json,
 };
-use mvr_types::name::VersionedName;
-use serde::{Deserialize, Serialize};
+use mvr_types::{
+    api::{BulkResolutionRequest, BulkResolutionResponse, ResolutionResponse},
+    name::VersionedName,
+};
 
 use crate::{data::resolution_loader::ResolutionKey, errors::ApiError, AppState};
 
 use super::validate_batch_size;
 
-#[derive(Serialize, Deserialize)]
-pub struct BulkRequest {
-    pub names: Vec<String>,
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct Response {
-    package_id: Option<String>,
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct BulkResponse {
-    resolution: HashMap<String, Response>,
-}
-
 pub struct Resolution;
 
 impl Resolution {
     pub async fn resolve(
         Path(name): Path<String>,
         State(app_state): State<Arc<AppState>>,
-    ) -> Result<Json<Response>, ApiError> {
+    ) -> Result<Json<ResolutionResponse>, ApiError> {
         let versioned_name = VersionedName::from_str(&name)?;
 
         let pkg = app_state
@@ -44,16 +31,16 @@ impl Resolution {
             .await?
             .ok_or(ApiError::BadRequest(format!("Name not found: {name}")))?;
 
-        Ok(Json(Response {
-            package_id: Some(pkg.id.to_string()),
+        Ok(Json(ResolutionResponse {
+            package_id: Some(pkg.id),
         }))
     }
 
     /// Resolve a list of names at once.
     pub async fn bulk_resolve(
         State(app_state): State<Arc<AppState>>,
-        Json(payload): Json<BulkRequest>,
-    ) -> Result<Json<BulkResponse>, ApiError> {
+        Json(payload): Json<BulkResolutionRequest>,
+    ) -> Result<Json<BulkResolutionResponse>, ApiError> {
         validate_batch_size(&payload.names, None)?;
         let names = payload
             .names
@@ -69,13 +56,13 @@ impl Resolution {
             .map(|(name, pkg)| {
                 (
                     name.0.to_string(),
-                    Response {
-                        package_id: Some(pkg.id.to_string()),
+                    ResolutionResponse {
+                        package_id: Some(pkg.id),
                     },
                 )
             })
-            .collect::<HashMap<String, Response>>();
+            .collect::<HashMap<String, ResolutionResponse>>();
 
-        Ok(Json(BulkResponse { resolution }))
+        Ok(Json(BulkResolutionResponse { resolution }))
     }
 }
@@ -1,9 +1,12 @@
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
 use axum::{
     extract::{Path, State},
     Json,
 };
+use mvr_types::api::{
+    BulkReverseResolutionRequest, BulkReverseResolutionResponse, ReverseResolutionResponse,
+};
 use serde::{Deserialize, Serialize};
 use sui_sdk_types::Address;
 
@@ -11,30 +14,16 @@ use crate::{data::reverse_resolution_loader::ReverseResolutionKey, errors::ApiEr
 
 use super::validate_batch_size;
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BulkRequest {
-    package_ids: Vec<Address>,
-}
-#[derive(Serialize, Deserialize)]
-pub struct BulkResponse {
-    resolution: HashMap<Address, Response>,
-}
-
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ResolvedName(pub Option<String>);
 
-#[derive(Serialize, Deserialize)]
-pub struct Response {
-    name: Option<String>,
-}
-
 pub struct ReverseResolution;
 
 impl ReverseResolution {
     pub async fn resolve(
         Path(package_id): Path<Address>,
         State(app_state): State<Arc<AppState>>,
-    ) -> Result<Json<Response>, ApiError> {
+    ) -> Result<Json<ReverseResolutionResponse>, ApiError> {
         let name = app_state
             .loader()
             .load_one(ReverseResolutionKey(package_id))
@@ -43,15 +32,15 @@ impl ReverseResolution {
                 "Name not found for package: {package_id}"
             )))?;
 
-        Ok(Json(Response {
+        Ok(Json(ReverseResolutionResponse {
             name: Some(name.to_string()),
         }))
     }
 
     pub async fn bulk_resolve(
         State(app_state): State<Arc<AppState>>,
-        Json(payload): Json<BulkRequest>,
-    ) -> Result<Json<BulkResponse>, ApiError> {
+        Json(payload): Json<BulkReverseResolutionRequest>,
+    ) -> Result<Json<BulkReverseResolutionResponse>, ApiError> {
         validate_batch_size(&payload.package_ids, None)?;
         let keys: Vec<_> = payload
             .package_ids
@@ -61,13 +50,13 @@ impl ReverseResolution {
 
         let results = app_state.loader().load_many(keys).await?;
 
-        Ok(Json(BulkResponse {
+        Ok(Json(BulkReverseResolutionResponse {
             resolution: results
                 .into_iter()
                 .map(|(key, name)| {
                     (
                         key.0,
-                        Response {
+                        ReverseResolutionResponse {
                             name: Some(name.to_string()),
                         },
                     )
@@ -7,8 +7,11 @@ use axum::{
     Json,
 };
 use move_core_types::language_storage::StructTag;
-use mvr_types::{name::VersionedName, named_type::NamedType};
-use serde::{Deserialize, Serialize};
+use mvr_types::{
+    api::{BulkTypesRequest, BulkTypesResponse, TypeResolutionResponse},
+    name::VersionedName,
+    named_type::NamedType,
+};
 
 use crate::{
     data::{
@@ -21,28 +24,13 @@ use crate::{
 
 use super::{into_object_id_map, validate_batch_size};
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BulkRequest {
-    types: Vec<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Response {
-    type_tag: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BulkResponse {
-    resolution: HashMap<String, Response>,
-}
-
 pub struct StructDefinition;
 
 impl StructDefinition {
     pub async fn resolve(
         Path(type_name): Path<String>,
         State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Response>, ApiError> {
+    ) -> Result<Json<TypeResolutionResponse>, ApiError> {
         verify_input(&type_name)?;
 
         let tags = bulk_resolve_definitions_impl(state, vec![type_name.clone()]).await?;
@@ -53,15 +41,15 @@ impl StructDefinition {
             .flatten()
             .ok_or(ApiError::BadRequest(format!("type not found: {type_name}")))?;
 
-        Ok(Json(Response {
+        Ok(Json(TypeResolutionResponse {
             type_tag: Some(tag),
         }))
     }
 
     pub async fn bulk_resolve(
         State(state): State<Arc<AppState>>,
-        Json(payload): Json<BulkRequest>,
-    ) -> Result<Json<BulkResponse>, ApiError> {
+        Json(payload): Json<BulkTypesRequest>,
+    ) -> Result<Json<BulkTypesResponse>, ApiError> {
         validate_batch_size(&payload.types, None)?;
 
         for type_name in payload.types.iter() {
@@ -70,10 +58,10 @@ impl StructDefinition {
 
         let tags = bulk_resolve_definitions_impl(state, payload.types).await?;
 
-        Ok(Json(BulkResponse {
+        Ok(Json(BulkTypesResponse {
             resolution: tags
                 .into_iter()
-                .map(|(k, type_tag)| (k, Response { type_tag }))
+                .map(|(k, type_tag)| (k, TypeResolutionResponse { type_tag }))
                 .collect(),
         }))
     }
@@ -6,8 +6,11 @@ use axum::{
     extract::{Path, State},
     Json,
 };
-use mvr_types::{name::VersionedName, named_type::NamedType};
-use serde::{Deserialize, Serialize};
+use mvr_types::{
+    api::{BulkTypesRequest, BulkTypesResponse, TypeResolutionResponse},
+    name::VersionedName,
+    named_type::NamedType,
+};
 use sui_types::TypeTag;
 
 use crate::{
@@ -18,28 +21,13 @@ use crate::{
 
 use super::{into_object_id_map, validate_batch_size};
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BulkRequest {
-    types: Vec<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Response {
-    type_tag: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BulkResponse {
-    resolution: HashMap<String, Response>,
-}
-
 pub struct TypeResolution;
 
 impl TypeResolution {
     pub async fn resolve(
         Path(type_name): Path<String>,
         State(state): State<Arc<AppState>>,
-    ) -> Result<Json<Response>, ApiError> {
+    ) -> Result<Json<TypeResolutionResponse>, ApiError> {
         let tags = bulk_resolve_types_impl(state, vec![type_name.clone()]).await?;
 
         let tag = tags
@@ -48,26 +36,26 @@ impl TypeResolution {
             .clone()
             .ok_or(ApiError::BadRequest(format!("type not found: {type_name}")))?;
 
-        Ok(Json(Response {
+        Ok(Json(TypeResolutionResponse {
             type_tag: Some(tag.to_canonical_string(true)),
         }))
     }
 
     pub async fn bulk_resolve(
         State(state): State<Arc<AppState>>,
-        Json(payload): Json<BulkRequest>,
-    ) -> Result<Json<BulkResponse>, ApiError> {
+        Json(payload): Json<BulkTypesRequest>,
+    ) -> Result<Json<BulkTypesResponse>, ApiError> {
         validate_batch_size(&payload.types, None)?;
 
         let tags = bulk_resolve_types_impl(state, payload.types).await?;
 
-        Ok(Json(BulkResponse {
+        Ok(Json(BulkTypesResponse {
             resolution: tags
                 .into_iter()
                 .map(|(k, v)| {
                     (
                         k,
-                        Response {
+                        TypeResolutionResponse {
                             type_tag: v.map(|t| t.to_canonical_string(true)),
                         },
                     )
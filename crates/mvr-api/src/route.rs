@@ -50,6 +50,7 @@ pub fn create_router(app: Arc<AppState>) -> Router {
             // Queries all names (paginated & can supply search query)
             get(Names::search_names),
         )
+        .route("/names/bulk", post(Names::bulk_get_by_name))
         .route("/names/analytics/{*name}", get(Names::get_analytics))
         .route("/names/{*name}", get(Names::get_by_name))
         .route(
@@ -63,6 +64,10 @@ pub fn create_router(app: Arc<AppState>) -> Router {
         .route(
             "/package-address/{package_address}/analytics",
             get(PackageAddress::analytics),
+        )
+        .route(
+            "/package-address/{package_address}/modules",
+            get(PackageAddress::modules),
         );
 
     Router::new()
@@ -1,10 +1,10 @@
 use base64::{engine::general_purpose, Engine};
+use mvr_types::api::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
 use serde::{Deserialize, Serialize};
 
 use crate::errors::ApiError;
 
-pub(crate) const DEFAULT_PAGE_LIMIT: u32 = 20;
-pub(crate) const MAX_PAGE_LIMIT: u32 = 50;
+pub use mvr_types::api::PaginatedResponse;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub struct PaginationLimit(u32);
@@ -67,37 +67,6 @@ impl Cursor {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PaginatedResponse<T> {
-    pub data: Vec<T>,
-    pub next_cursor: Option<String>,
-    pub limit: u32,
-    /// Total is optional, and only returned when the count is also retrievable.
-    pub total: Option<i64>,
-}
-
-impl<T> PaginatedResponse<T> {
-    pub fn new(
-        mut data: Vec<T>,
-        next_cursor: Option<String>,
-        limit: u32,
-        total: Option<i64>,
-    ) -> Self {
-        // if the result has more items than the limit, we pop the last item, as it
-        // is only used to determine if there is a next page
-        if data.len() > limit as usize {
-            data.pop();
-        }
-
-        Self {
-            data,
-            next_cursor,
-            limit,
-            total,
-        }
-    }
-}
-
 /// Paginate the results of a query, by encoding the last (functional) item as the
 /// next cursor's definition.
 ///
@@ -1,16 +1,34 @@
+use std::str::FromStr;
+
 use insta::assert_snapshot;
+use mvr_client::MvrClientError;
 use mvr_test_cluster::{add_name_record_to_database, MvrTestCluster};
-use reqwest::StatusCode;
+use mvr_types::api::NameSearchQueryParams;
+use sui_sdk_types::Address;
 use sui_types::base_types::ObjectID;
 #[cfg(test)]
 mod mvr_test_cluster;
 
-fn v1_id() -> String {
-    ObjectID::from_single_byte(0xc1).to_canonical_string(true)
+fn v1_id() -> Address {
+    Address::from_str(&ObjectID::from_single_byte(0xc1).to_canonical_string(true)).unwrap()
+}
+
+fn v2_id() -> Address {
+    Address::from_str(&ObjectID::from_single_byte(0xc2).to_canonical_string(true)).unwrap()
 }
 
-fn v2_id() -> String {
-    ObjectID::from_single_byte(0xc2).to_canonical_string(true)
+fn search_params(
+    search: Option<&str>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+) -> NameSearchQueryParams {
+    NameSearchQueryParams {
+        search: search.map(str::to_string),
+        cursor,
+        limit,
+        is_linked: None,
+        org: None,
+    }
 }
 
 #[tokio::test]
@@ -19,21 +37,16 @@ async fn test_lookups_regular() -> Result<(), anyhow::Error> {
     test_cluster.setup_dummy_data().await?;
 
     let mut response = test_cluster
-        .bulk_resolution(&["@test/core", "@test/core/1"])
+        .client
+        .bulk_resolve(&["@test/core", "@test/core/1"])
         .await?;
 
-    assert_eq!(
-        response["@test/core"]["package_id"].as_str().unwrap(),
-        v2_id()
-    );
-    assert_eq!(
-        response["@test/core/1"]["package_id"].as_str().unwrap(),
-        v1_id()
-    );
+    assert_eq!(response["@test/core"].package_id, Some(v2_id()));
+    assert_eq!(response["@test/core/1"].package_id, Some(v1_id()));
 
-    response = test_cluster.bulk_resolution(&["@test/core/3"]).await?;
+    response = test_cluster.client.bulk_resolve(&["@test/core/3"]).await?;
 
-    assert!(response["@test/core/3"]["package_id"].is_null());
+    assert!(!response.contains_key("@test/core/3"));
 
     test_cluster.teardown();
     Ok(())
@@ -43,8 +56,8 @@ async fn test_lookups_regular() -> Result<(), anyhow::Error> {
 async fn test_lookups_non_existent() -> Result<(), anyhow::Error> {
     let test_cluster = MvrTestCluster::new(None).await?;
 
-    let response = test_cluster.bulk_resolution(&["@random/name"]).await?;
-    assert!(response["@random/name"]["package_id"].is_null());
+    let response = test_cluster.client.bulk_resolve(&["@random/name"]).await?;
+    assert!(!response.contains_key("@random/name"));
 
     test_cluster.teardown();
     Ok(())
@@ -55,13 +68,20 @@ async fn test_reverse_resolution() -> Result<(), anyhow::Error> {
     let test_cluster = MvrTestCluster::new(None).await?;
     test_cluster.setup_dummy_data().await?;
 
+    let package_ids = ["0xc1", "0xc2", "0x1"]
+        .into_iter()
+        .map(Address::from_str)
+        .chain([Ok(v1_id())]) // we dedup inside the API.
+        .collect::<Result<Vec<_>, _>>()?;
+
     let response = test_cluster
-        .bulk_reverse_resolution(&["0xc1", "0xc2", "0x1", &v1_id()]) // we dedup inside the API.
+        .client
+        .bulk_reverse_resolve(&package_ids)
         .await?;
 
-    assert_eq!(response[v2_id()]["name"].as_str().unwrap(), "@test/core");
-    assert_eq!(response[v1_id()]["name"].as_str().unwrap(), "@test/core");
-    assert!(response[ObjectID::from_single_byte(0x1).to_canonical_string(true)]["name"].is_null());
+    assert_eq!(response[&v2_id()].name.as_deref(), Some("@test/core"));
+    assert_eq!(response[&v1_id()].name.as_deref(), Some("@test/core"));
+    assert!(!response.contains_key(&Address::from_str("0x1")?));
 
     test_cluster.teardown();
     Ok(())
@@ -73,7 +93,8 @@ async fn test_type_resolution() -> Result<(), anyhow::Error> {
     test_cluster.setup_dummy_data().await?;
 
     let response = test_cluster
-        .bulk_type_resolution(&[
+        .client
+        .bulk_resolve_types(&[
             "@test/core::c::C",
             "@test/core::c::D",
             "@test/core::c::WPhantomTypeParam",
@@ -83,25 +104,25 @@ async fn test_type_resolution() -> Result<(), anyhow::Error> {
 
     // check V1 resolution works by name.
     assert_eq!(
-        response["@test/core::c::C"]["type_tag"].as_str().unwrap(),
-        format!("{}::c::C", v1_id())
+        response["@test/core::c::C"].type_tag,
+        Some(format!("{}::c::C", v1_id()))
     );
 
     // Check V2 resolution works by name.
     assert_eq!(
-        response["@test/core::c::D"]["type_tag"].as_str().unwrap(),
-        format!("{}::c::D", v2_id())
+        response["@test/core::c::D"].type_tag,
+        Some(format!("{}::c::D", v2_id()))
     );
 
     // This won't work on the type resolution API, we need to go through the "Struct definition" API.
-    assert!(response["@test/core::c::WPhantomTypeParam"]["type_tag"].is_null());
+    assert!(response["@test/core::c::WPhantomTypeParam"]
+        .type_tag
+        .is_none());
 
     // Check V2 resolution works by name.
     assert_eq!(
-        response["@test/core::c::WPhantomTypeParam<u64>"]["type_tag"]
-            .as_str()
-            .unwrap(),
-        format!("{}::c::WPhantomTypeParam<u64>", v1_id())
+        response["@test/core::c::WPhantomTypeParam<u64>"].type_tag,
+        Some(format!("{}::c::WPhantomTypeParam<u64>", v1_id()))
     );
 
     test_cluster.teardown();
@@ -115,7 +136,8 @@ async fn test_struct_definition() -> Result<(), anyhow::Error> {
     test_cluster.setup_dummy_data().await?;
 
     let response = test_cluster
-        .bulk_struct_definition(&[
+        .client
+        .bulk_struct_definitions(&[
             "@test/core::c::C",
             "@test/core::c::D",
             "@test/core::c::WPhantomTypeParam",
@@ -124,22 +146,20 @@ async fn test_struct_definition() -> Result<(), anyhow::Error> {
 
     // check V1 resolution works by name.
     assert_eq!(
-        response["@test/core::c::C"]["type_tag"].as_str().unwrap(),
-        format!("{}::c::C", v1_id())
+        response["@test/core::c::C"].type_tag,
+        Some(format!("{}::c::C", v1_id()))
     );
 
     // Check V2 resolution works by name.
     assert_eq!(
-        response["@test/core::c::D"]["type_tag"].as_str().unwrap(),
-        format!("{}::c::D", v2_id())
+        response["@test/core::c::D"].type_tag,
+        Some(format!("{}::c::D", v2_id()))
     );
 
     // In the struct definition API, now we can query without the generic type params.
     assert_eq!(
-        response["@test/core::c::WPhantomTypeParam"]["type_tag"]
-            .as_str()
-            .unwrap(),
-        format!("{}::c::WPhantomTypeParam", v1_id())
+        response["@test/core::c::WPhantomTypeParam"].type_tag,
+        Some(format!("{}::c::WPhantomTypeParam", v1_id()))
     );
 
     test_cluster.teardown();
@@ -151,28 +171,89 @@ async fn test_package_by_name() -> Result<(), anyhow::Error> {
     let test_cluster = MvrTestCluster::new(None).await?;
     test_cluster.setup_dummy_data().await?;
 
-    let (_, v2) = test_cluster.package_by_name("@test/core").await?;
-    assert_snapshot!(v2.to_string());
+    let v2 = test_cluster.client.package_by_name("@test/core").await?;
+    assert_snapshot!(serde_json::to_string(&v2)?);
 
-    let (_, v1) = test_cluster.package_by_name("@test/core/1").await?;
-    assert_snapshot!(v1.to_string());
+    let v1 = test_cluster.client.package_by_name("@test/core/1").await?;
+    assert_snapshot!(serde_json::to_string(&v1)?);
 
-    let (status, non_existent) = test_cluster.package_by_name("@test/does-not-exist").await?;
+    let non_existent = test_cluster
+        .client
+        .package_by_name("@test/does-not-exist")
+        .await
+        .unwrap_err();
 
-    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(non_existent.status(), Some(404));
     assert_snapshot!(non_existent.to_string());
 
     test_cluster.teardown();
     Ok(())
 }
 
+#[tokio::test]
+async fn test_bulk_package_by_name() -> Result<(), anyhow::Error> {
+    let test_cluster = MvrTestCluster::new(None).await?;
+    test_cluster.setup_dummy_data().await?;
+
+    let response = test_cluster
+        .client
+        .bulk_package_by_name(&[
+            "@test/core",
+            "@test/core/1",
+            "@test/does-not-exist",
+            "not a valid name",
+        ])
+        .await?;
+
+    // The bulk endpoint returns the same data as the single name lookups.
+    let v2 = test_cluster.client.package_by_name("@test/core").await?;
+    let v1 = test_cluster.client.package_by_name("@test/core/1").await?;
+
+    assert_eq!(response["@test/core"], Some(v2));
+    assert_eq!(response["@test/core/1"], Some(v1));
+    assert!(response["@test/does-not-exist"].is_none());
+    // A malformed name does not fail the batch; it resolves to `null` like a missing one.
+    assert!(response["not a valid name"].is_none());
+
+    test_cluster.teardown();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_package_modules() -> Result<(), anyhow::Error> {
+    let test_cluster = MvrTestCluster::new(None).await?;
+    test_cluster.setup_dummy_data().await?;
+
+    let v2 = test_cluster.client.package_modules(&v2_id()).await?;
+
+    assert_eq!(v2.package_id, v2_id());
+    assert_eq!(v2.original_id, v1_id());
+    assert_eq!(v2.version, 2);
+
+    assert_eq!(v2.modules.keys().collect::<Vec<_>>(), vec!["c"]);
+    assert!(!v2.modules["c"].is_empty());
+
+    let err = test_cluster
+        .client
+        .package_modules(&Address::from_str("0x1234")?)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MvrClientError::NotFound(_)));
+
+    test_cluster.teardown();
+    Ok(())
+}
+
 #[tokio::test]
 async fn basic_search() -> Result<(), anyhow::Error> {
     let test_cluster = MvrTestCluster::new(None).await?;
     test_cluster.setup_dummy_data().await?;
 
-    let basic_dummy_data = test_cluster.search_names(None, None, None).await?;
-    assert_snapshot!(basic_dummy_data.to_string());
+    let basic_dummy_data = test_cluster
+        .client
+        .search_names(&NameSearchQueryParams::default())
+        .await?;
+    assert_snapshot!(serde_json::to_string(&basic_dummy_data)?);
 
     test_cluster.teardown();
 
@@ -184,15 +265,20 @@ async fn test_bulk_lookup_limit_exceeded() -> Result<(), anyhow::Error> {
     let test_cluster = MvrTestCluster::new(None).await?;
     test_cluster.setup_dummy_data().await?;
 
-    let object_ids = vec![ObjectID::from_single_byte(0xc1).to_string(); 51];
+    let package_ids = vec![v1_id(); 51];
+
+    // The client splits the package ids in batches the API accepts.
+    let response = test_cluster
+        .client
+        .bulk_reverse_resolve(&package_ids)
+        .await?;
+    assert_eq!(response[&v1_id()].name.as_deref(), Some("@test/core"));
+
     let err = test_cluster
-        .bulk_reverse_resolution(
-            object_ids
-                .iter()
-                .map(|id| id.as_str())
-                .collect::<Vec<_>>()
-                .as_slice(),
-        )
+        .client
+        .clone()
+        .with_batch_size(51)
+        .bulk_reverse_resolve(&package_ids)
         .await
         .unwrap_err();
 
@@ -218,59 +304,99 @@ async fn test_search_with_filters() -> Result<(), anyhow::Error> {
 
     add_name_record_to_database(&mut db, "@final/round", None, None, None).await?;
 
-    let generic_query = test_cluster.search_names(None, None, None).await?;
+    let generic_query = test_cluster
+        .client
+        .search_names(&search_params(None, None, None))
+        .await?;
     // no "filters", we should have all 6 names in the query.
-    assert_eq!(generic_query["data"].as_array().unwrap().len(), 6);
-    assert!(generic_query["next_cursor"].is_null());
+    assert_eq!(generic_query.data.len(), 6);
+    assert!(generic_query.next_cursor.is_none());
+
+    // following the cursors returns the same names.
+    let all_names = test_cluster.client.all_names(None, None).await?;
+    assert_eq!(all_names, generic_query.data);
 
     // now let's change "limit" to be 3, and check we can paginate in two pages.
-    let mut query = test_cluster.search_names(None, None, Some(3)).await?;
-    assert_eq!(query["data"].as_array().unwrap().len(), 3);
-    assert!(query["next_cursor"].is_string());
+    let mut query = test_cluster
+        .client
+        .search_names(&search_params(None, None, Some(3)))
+        .await?;
+    assert_eq!(query.data.len(), 3);
+    assert!(query.next_cursor.is_some());
 
-    let next_cursor = query["next_cursor"].as_str().unwrap().to_string();
+    let next_cursor = query.next_cursor.clone();
 
     // now let's paginate again.
     query = test_cluster
-        .search_names(None, Some(next_cursor), Some(3))
+        .client
+        .search_names(&search_params(None, next_cursor, Some(3)))
         .await?;
-    assert_eq!(query["data"].as_array().unwrap().len(), 3);
-    assert!(query["next_cursor"].is_null());
+    assert_eq!(query.data.len(), 3);
+    assert!(query.next_cursor.is_none());
 
     // now let's change the search query to be "@test"
     query = test_cluster
-        .search_names(Some("@test".to_string()), None, None)
+        .client
+        .search_names(&search_params(Some("@test"), None, None))
         .await?;
-    assert_eq!(query["data"].as_array().unwrap().len(), 3);
-    assert!(query["next_cursor"].is_null());
+    assert_eq!(query.data.len(), 3);
+    assert!(query.next_cursor.is_none());
 
     // now let's change the search query to be "@another"
     query = test_cluster
-        .search_names(Some("@another".to_string()), None, None)
+        .client
+        .search_names(&search_params(Some("@another"), None, None))
         .await?;
-    assert_eq!(query["data"].as_array().unwrap().len(), 2);
-    assert!(query["next_cursor"].is_null());
+    assert_eq!(query.data.len(), 2);
+    assert!(query.next_cursor.is_none());
 
     // now let's change the search query to be "@final"
     query = test_cluster
-        .search_names(Some("@final".to_string()), None, None)
+        .client
+        .search_names(&search_params(Some("@final"), None, None))
+        .await?;
+    assert_eq!(query.data.len(), 1);
+    assert!(query.next_cursor.is_none());
+
+    // only names of the `@test` organization, whatever the search query.
+    query = test_cluster
+        .client
+        .search_names(&NameSearchQueryParams {
+            org: Some("test.sui".to_string()),
+            ..search_params(Some("t"), None, None)
+        })
         .await?;
-    assert_eq!(query["data"].as_array().unwrap().len(), 1);
-    assert!(query["next_cursor"].is_null());
+    assert_eq!(query.data.len(), 3);
+    assert!(query
+        .data
+        .iter()
+        .all(|name| name.name.starts_with("@test/")));
+
+    // organizations are matched exactly: `_` is not a wildcard (and not a valid label character).
+    assert!(test_cluster
+        .client
+        .search_names(&NameSearchQueryParams {
+            org: Some("te_t.sui".to_string()),
+            ..search_params(Some("t"), None, None)
+        })
+        .await
+        .is_err());
 
     // now let's change the search query to include names that have `fi` in them.
     query = test_cluster
-        .search_names(Some("fi".to_string()), None, None)
+        .client
+        .search_names(&search_params(Some("fi"), None, None))
         .await?;
-    assert_eq!(query["data"].as_array().unwrap().len(), 2);
-    assert!(query["next_cursor"].is_null());
+    assert_eq!(query.data.len(), 2);
+    assert!(query.next_cursor.is_none());
 
     // now let's change the search query to include names that have `final` in them.
     query = test_cluster
-        .search_names(Some("final".to_string()), None, None)
+        .client
+        .search_names(&search_params(Some("final"), None, None))
         .await?;
-    assert_eq!(query["data"].as_array().unwrap().len(), 1);
-    assert!(query["next_cursor"].is_null());
+    assert_eq!(query.data.len(), 1);
+    assert!(query.next_cursor.is_none());
 
     test_cluster.teardown();
 
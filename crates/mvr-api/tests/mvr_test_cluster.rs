@@ -8,12 +8,12 @@ use diesel::insert_into;
 use diesel_async::RunQueryDsl;
 use move_binary_format::CompiledModule;
 use mvr_api::run_server;
+use mvr_client::MvrClient;
 use mvr_schema::{
     models::{NameRecord, Package, PackageInfo},
     schema::{name_records, package_infos, packages},
     MIGRATIONS,
 };
-use reqwest::{Client, StatusCode};
 use serde_json::json;
 use sui_move_build::{BuildConfig, CompiledPackage};
 use sui_pg_db::{temp::TempDb, Db, DbArgs};
@@ -27,7 +27,7 @@ use url::Url;
 pub(crate) struct MvrTestCluster {
     pub cancellation_token: CancellationToken,
     pub db: TempDb,
-    pub client: Client,
+    pub client: MvrClient,
     pub server_handle: JoinHandle<()>,
     pub server_url: Url,
 }
@@ -56,136 +56,6 @@ impl MvrTestCluster {
         Ok(test_cluster)
     }
 
-    pub async fn bulk_resolution(
-        &self,
-        names: &[&str],
-    ) -> Result<serde_json::Value, anyhow::Error> {
-        let res = self
-            .client
-            .post(format!("{}v1/resolution/bulk", self.server_url.as_str()).parse::<Url>()?)
-            .json(&json!({
-                "names": names
-            }))
-            .send()
-            .await?;
-
-        let res_body = res.json::<serde_json::Value>().await?["resolution"].clone();
-
-        Ok(res_body)
-    }
-
-    pub async fn bulk_reverse_resolution(
-        &self,
-        package_ids: &[&str],
-    ) -> Result<serde_json::Value, anyhow::Error> {
-        let res = self
-            .client
-            .post(format!("{}v1/reverse-resolution/bulk", self.server_url.as_str()).parse::<Url>()?)
-            .json(&json!({
-                "package_ids": package_ids
-            }))
-            .send()
-            .await?;
-
-        if res.status().is_success() {
-            let res_body = res.json::<serde_json::Value>().await?["resolution"].clone();
-            Ok(res_body)
-        } else {
-            let error = res.json::<serde_json::Value>().await?;
-            Err(anyhow::anyhow!("Error: {}", error))
-        }
-    }
-
-    pub async fn bulk_type_resolution(
-        &self,
-        types: &[&str],
-    ) -> Result<serde_json::Value, anyhow::Error> {
-        let res = self
-            .client
-            .post(format!("{}v1/type-resolution/bulk", self.server_url.as_str()).parse::<Url>()?)
-            .json(&json!({
-                "types": types
-            }))
-            .send()
-            .await?;
-
-        let res_body = res.json::<serde_json::Value>().await?["resolution"].clone();
-
-        Ok(res_body)
-    }
-
-    pub async fn bulk_struct_definition(
-        &self,
-        struct_names: &[&str],
-    ) -> Result<serde_json::Value, anyhow::Error> {
-        let res = self
-            .client
-            .post(format!("{}v1/struct-definition/bulk", self.server_url.as_str()).parse::<Url>()?)
-            .json(&json!({
-                "types": struct_names
-            }))
-            .send()
-            .await?;
-
-        let res_body = res.json::<serde_json::Value>().await?["resolution"].clone();
-
-        Ok(res_body)
-    }
-
-    pub async fn package_by_name(
-        &self,
-        name: &str,
-    ) -> Result<(StatusCode, serde_json::Value), anyhow::Error> {
-        let res = self
-            .client
-            .get(format!("{}v1/names/{}", self.server_url.as_str(), name).parse::<Url>()?)
-            .send()
-            .await?;
-
-        let status = res.status();
-        let res_body = res.json::<serde_json::Value>().await?;
-
-        Ok((status, res_body))
-    }
-
-    pub async fn search_names(
-        &self,
-        search: Option<String>,
-        cursor: Option<String>,
-        limit: Option<u32>,
-    ) -> Result<serde_json::Value, anyhow::Error> {
-        let cursor_str = if let Some(cursor) = cursor {
-            format!("&cursor={}", cursor)
-        } else {
-            "".to_string()
-        };
-
-        let limit_str = if let Some(limit) = limit {
-            format!("&limit={}", limit)
-        } else {
-            "".to_string()
-        };
-
-        let res = self
-            .client
-            .get(
-                format!(
-                    "{}v1/names?search={}{}{}",
-                    self.server_url.as_str(),
-                    search.unwrap_or_default(),
-                    cursor_str,
-                    limit_str
-                )
-                .parse::<Url>()?,
-            )
-            .send()
-            .await?;
-
-        let res_body = res.json::<serde_json::Value>().await?;
-
-        Ok(res_body)
-    }
-
     pub fn teardown(&self) {
         self.server_handle.abort();
         self.cancellation_token.cancel();
@@ -220,7 +90,8 @@ async fn setup(port: Option<u16>) -> Result<MvrTestCluster, anyhow::Error> {
         .await;
     });
 
-    let client = Client::new();
+    let server_url: Url = format!("http://127.0.0.1:{}", assigned_port).parse()?;
+    let client = MvrClient::new(server_url.as_str());
 
     // Migrate initial set of data that can be used for testing multiple queries.
     Ok(MvrTestCluster {
@@ -228,7 +99,7 @@ async fn setup(port: Option<u16>) -> Result<MvrTestCluster, anyhow::Error> {
         db: temp_db,
         client,
         server_handle,
-        server_url: format!("http://127.0.0.1:{}", assigned_port).parse()?,
+        server_url,
     })
 }
 
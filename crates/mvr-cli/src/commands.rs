@@ -1,31 +1,177 @@
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::path::PathBuf;
 use yansi::Paint;
 
 use anyhow::Result;
-use clap::Subcommand;
+use clap::{Args, Subcommand};
 
 use serde::Serialize;
 
 use crate::subcommand_add_dependency;
+use crate::subcommand_cache_clear;
+use crate::subcommand_cache_list;
+use crate::subcommand_check;
+use crate::subcommand_doctor;
+use crate::subcommand_list_dependencies;
+use crate::subcommand_name_info;
+use crate::subcommand_outdated_dependencies;
+use crate::subcommand_remove_dependency;
 use crate::subcommand_resolve_name;
+use crate::subcommand_resolve_structs;
+use crate::subcommand_resolve_types;
 use crate::subcommand_search_names;
+use crate::subcommand_update_dependencies;
+use crate::subcommand_verify;
+use crate::subcommand_whois;
 use crate::types::api_types::PackageRequest;
 use crate::types::api_types::SearchNamesResponse;
+use crate::types::AddOptions;
+use crate::types::CachedResolutionKind;
+use crate::types::CachedResolutions;
+use crate::types::CheckStatus;
+use crate::types::ClearedCache;
+use crate::types::DependencyCheck;
+use crate::types::DoctorReport;
+use crate::types::ListedDependencies;
+use crate::types::ModuleStatus;
+use crate::types::NameInfo;
 use crate::types::Network;
+use crate::types::OutdatedDependencies;
+use crate::types::PackageVerification;
+use crate::types::RemovedDependency;
+use crate::types::ResolvedTypes;
+use crate::types::ReverseResolvedNames;
+use crate::types::UpdatedDependencies;
+use crate::types::WriteOptions;
+use crate::utils::diff::colorize_diff;
+use crate::utils::resolution_cache::now;
+use crate::utils::sparkline::sparkline;
+
+/// The metadata keys (and their labels) shown for every search result.
+const SEARCH_METADATA_KEYS: [(&str, &str); 3] = [
+    ("homepage_url", "Homepage"),
+    ("documentation_url", "Documentation"),
+    ("icon_url", "Icon"),
+];
 
 #[derive(Serialize, Subcommand)]
 #[serde()]
 pub enum Command {
-    /// Add a new dependency from the move registry to your Move.toml file.
-    Add { name: String },
+    /// Add new dependencies from the move registry to your Move.toml file.
+    #[command(disable_version_flag = true)]
+    Add {
+        /// The MVR names to add (e.g. `@org/app`, or `@org/app/3` for a pinned version).
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// The key of the dependency in your Move.toml. Defaults to the name of the package.
+        #[arg(long = "as", value_name = "ALIAS")]
+        alias: Option<String>,
+        /// Pin the dependency to this version.
+        #[arg(long)]
+        version: Option<u64>,
+        /// Resolve the dependency on this network, and record it as the network of the dependency.
+        /// Defaults to the active network of the Sui CLI.
+        #[arg(short, long)]
+        network: Option<Network>,
+        /// Overwrite a different dependency with the same key.
+        #[arg(long)]
+        force: bool,
+        /// Add the dependency to every Move.toml file beneath this workspace root.
+        #[arg(long, value_name = "ROOT")]
+        workspace: Option<PathBuf>,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// Remove a dependency from the move registry from your Move.toml file.
+    Remove {
+        /// The MVR name (e.g. `@org/app`) or the alias of the dependency to remove.
+        name: String,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// List all dependencies from the move registry in your Move.toml file.
+    List {
+        /// The network to resolve the dependencies on. Defaults to the active network of the Sui CLI.
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
+    /// Check the version-pinned dependencies of your Move.toml file for newer versions.
+    Outdated {
+        /// The network to check the latest versions on. Defaults to the active network of the Sui CLI.
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
+    /// Check the dependencies of your Move.toml file for duplicates and version skew.
+    Check {
+        /// The network to check the dependencies on. Defaults to the active network of the Sui CLI.
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
+    /// Update the version-pinned dependencies of your Move.toml file to their latest versions.
+    Update {
+        /// The MVR name (e.g. `@org/app`) or the alias of the dependency to update.
+        /// Updates all version-pinned dependencies if not supplied.
+        name: Option<String>,
+        /// The network to check the latest versions on. Defaults to the active network of the Sui CLI.
+        #[arg(short, long)]
+        network: Option<Network>,
+        /// Never update a pin past this version. MVR versions are a single, incrementing number,
+        /// so this is the equivalent of limiting updates to a given range.
+        #[arg(long)]
+        max_version: Option<u64>,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
     /// Resolve the app name to a package info.
     Resolve {
         name: String,
         #[arg(short, long)]
         network: Option<Network>,
     },
+    /// Show the metadata, version history, dependencies and dependents of an app.
+    Info {
+        name: String,
+        #[arg(short, long)]
+        network: Option<Network>,
+        /// The number of top dependents to show (at most 50).
+        #[arg(long, default_value_t = 10)]
+        dependents: u32,
+    },
+    /// Resolve types with MVR names (e.g. `@org/app::module::Type`) to their canonical type tags.
+    Type {
+        /// The types to resolve.
+        types: Vec<String>,
+        /// A file with types to resolve, one per line.
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
+    /// Resolve types with MVR names to their struct tags, using the defining IDs of their packages.
+    Struct {
+        /// The types to resolve.
+        types: Vec<String>,
+        /// A file with types to resolve, one per line.
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
+    /// Find the MVR names of package addresses.
+    Whois {
+        /// The package addresses to look up. If none are given, they are read from stdin.
+        addresses: Vec<String>,
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
+    /// Verify that the on-chain bytecode of an app matches its registered git source.
+    Verify {
+        name: String,
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
     /// Search for an app in the move registry.
     Search {
         /// Your search query. Expects a partial package name or description.
@@ -37,6 +183,69 @@ pub enum Command {
         /// The cursor to paginate through the results. Defaults to the first page.
         #[arg(short, long)]
         cursor: Option<String>,
+        /// The network whose MVR API is queried. Defaults to mainnet.
+        #[arg(short, long)]
+        network: Option<Network>,
+        /// Only return names that are linked to a package on at least one network.
+        #[arg(long)]
+        linked_only: bool,
+        /// Only return names of the given organization (e.g. `@mvr` or `mvr.sui`).
+        #[arg(long)]
+        org: Option<String>,
+    },
+    /// Diagnose your environment (Sui CLI, network, MVR API and package caching).
+    Doctor {
+        /// The network to check. Defaults to the active network of the Sui CLI.
+        #[arg(short, long)]
+        network: Option<Network>,
+        /// An MVR name to resolve and cache with `sui move cache-package`.
+        /// Defaults to the first MVR dependency of the Move.toml in the current directory.
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Inspect or clear the local cache of resolved names and package addresses.
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+/// The options of the commands that change Move.toml files.
+#[derive(Serialize, Args)]
+pub struct WriteArgs {
+    /// Print the changes that would be made to your Move.toml, without applying them.
+    #[arg(long)]
+    dry_run: bool,
+    /// Keep the previous version of your Move.toml as `Move.toml.bak`.
+    #[arg(long, conflicts_with = "dry_run")]
+    backup: bool,
+}
+
+impl From<WriteArgs> for WriteOptions {
+    fn from(args: WriteArgs) -> Self {
+        Self {
+            dry_run: args.dry_run,
+            backup: args.backup,
+        }
+    }
+}
+
+#[derive(Serialize, Subcommand)]
+pub enum CacheCommand {
+    /// List the cached resolutions, and when they expire.
+    Ls {
+        /// Only list the resolutions of this network.
+        #[arg(short, long)]
+        network: Option<Network>,
+    },
+    /// Remove the cached resolutions, so names are resolved through the MVR API again.
+    Clear {
+        /// Only remove the resolutions of this network.
+        #[arg(short, long)]
+        network: Option<Network>,
+        /// Remove the whole local cache, including the packages cached for offline builds.
+        #[arg(long, conflicts_with = "network")]
+        all: bool,
     },
 }
 
@@ -44,20 +253,99 @@ pub enum Command {
 pub enum CommandOutput {
     #[serde(rename = "added_to_toml")]
     Add(String),
+    #[serde(rename = "removed_from_toml")]
+    Remove(RemovedDependency),
+    List(ListedDependencies),
+    Outdated(OutdatedDependencies),
+    Update(UpdatedDependencies),
     Resolve(PackageRequest),
+    Info(NameInfo),
+    Type(ResolvedTypes),
+    Struct(ResolvedTypes),
+    Whois(ReverseResolvedNames),
+    Verify(PackageVerification),
     Search(SearchNamesResponse),
+    Doctor(DoctorReport),
+    Check(DependencyCheck),
+    CacheList(CachedResolutions),
+    CacheClear(ClearedCache),
 }
 
 impl Command {
     pub async fn execute(self) -> Result<CommandOutput> {
         match self {
-            Command::Add { name } => subcommand_add_dependency(&name).await,
+            Command::Add {
+                names,
+                alias,
+                version,
+                network,
+                force,
+                workspace,
+                write,
+            } => {
+                let options = AddOptions {
+                    alias,
+                    version,
+                    network,
+                    force,
+                    write: write.into(),
+                };
+                subcommand_add_dependency(&names, options, workspace).await
+            }
+            Command::Remove { name, write } => subcommand_remove_dependency(&name, write.into()),
+            Command::List { network } => subcommand_list_dependencies(network).await,
+            Command::Outdated { network } => subcommand_outdated_dependencies(network).await,
+            Command::Check { network } => subcommand_check(network).await,
+            Command::Update {
+                name,
+                network,
+                max_version,
+                write,
+            } => subcommand_update_dependencies(name, network, max_version, write.into()).await,
             Command::Resolve { name, network } => subcommand_resolve_name(&name, network).await,
+            Command::Info {
+                name,
+                network,
+                dependents,
+            } => subcommand_name_info(&name, network, dependents).await,
+            Command::Type {
+                types,
+                file,
+                network,
+            } => subcommand_resolve_types(types, file, network).await,
+            Command::Struct {
+                types,
+                file,
+                network,
+            } => subcommand_resolve_structs(types, file, network).await,
+            Command::Whois { addresses, network } => subcommand_whois(addresses, network).await,
+            Command::Verify { name, network } => subcommand_verify(&name, network).await,
             Command::Search {
                 query,
                 limit,
                 cursor,
-            } => subcommand_search_names(query, limit, cursor).await,
+                network,
+                linked_only,
+                org,
+            } => subcommand_search_names(query, limit, cursor, network, linked_only, org).await,
+            Command::Doctor { network, name } => subcommand_doctor(network, name).await,
+            Command::Cache { command } => match command {
+                CacheCommand::Ls { network } => subcommand_cache_list(network),
+                CacheCommand::Clear { network, all } => subcommand_cache_clear(network, all),
+            },
+        }
+    }
+}
+
+impl CommandOutput {
+    /// Whether the command ran, but its result should make the CLI exit with an error
+    /// (e.g. a failed verification).
+    pub fn is_failure(&self) -> bool {
+        match self {
+            CommandOutput::Verify(verification) => !verification.verified,
+            CommandOutput::Doctor(report) => report.has_failures(),
+            CommandOutput::Check(check) => !check.conflicts.is_empty(),
+            _ => false,
         }
     }
 }
@@ -66,10 +354,339 @@ impl Display for CommandOutput {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             CommandOutput::Add(output) => write!(f, "{}", output),
+            CommandOutput::Remove(removed) => {
+                let dependency = &removed.dependency;
+
+                if removed.dry_run {
+                    if let Some(diff) = &removed.diff {
+                        writeln!(f, "{}", colorize_diff(diff))?;
+                    }
+
+                    return writeln!(
+                        f,
+                        "\nWould remove dependency {} ({}) from your Move.toml",
+                        dependency.name.green(),
+                        dependency.alias.bold()
+                    );
+                }
+
+                writeln!(
+                    f,
+                    "\nSuccessfully removed dependency {} ({}) from your Move.toml",
+                    dependency.name.green(),
+                    dependency.alias.bold()
+                )?;
+                let hint = format!(
+                    "Make sure to remove any `use {}::<module>;` statements from your modules.",
+                    dependency.alias
+                );
+                writeln!(f, "{}", hint.italic())
+            }
+            CommandOutput::List(listed) => {
+                if listed.dependencies.is_empty() {
+                    return writeln!(
+                        f,
+                        "{}",
+                        "No move registry dependencies found in your Move.toml.".italic()
+                    );
+                }
+
+                for dep in listed.dependencies.iter() {
+                    writeln!(
+                        f,
+                        "\n- {} {}",
+                        dep.dependency.alias.bold(),
+                        dep.dependency.name.green()
+                    )?;
+
+                    let pinned_version = dep
+                        .pinned_version
+                        .map(|v| v.to_string())
+                        .unwrap_or("latest".italic().to_string());
+                    writeln!(f, "  Pinned version:  {}", pinned_version)?;
+
+                    let (Some(version), Some(address)) =
+                        (dep.resolved_version, &dep.package_address)
+                    else {
+                        writeln!(f, "  {}", "Not resolved".yellow())?;
+                        continue;
+                    };
+
+                    writeln!(f, "  Version:         {}", version)?;
+                    writeln!(f, "  Package address: {}", address)?;
+
+                    if let Some(git_info) = &dep.git_info {
+                        writeln!(
+                            f,
+                            "  Repository:      {}",
+                            git_info.repository_url.as_deref().unwrap_or("--")
+                        )?;
+                        writeln!(
+                            f,
+                            "  Rev:             {}",
+                            git_info.tag.as_deref().unwrap_or("--")
+                        )?;
+                    }
+                }
+
+                if let Some(network) = &listed.network {
+                    writeln!(f, "\nResolved on network: {}", network.blue())?;
+                }
+
+                Ok(())
+            }
+            CommandOutput::Outdated(outdated) => {
+                if outdated.dependencies.is_empty() {
+                    return writeln!(
+                        f,
+                        "{}",
+                        "No version-pinned move registry dependencies found in your Move.toml."
+                            .italic()
+                    );
+                }
+
+                for dep in outdated.dependencies.iter() {
+                    let status = if dep.is_outdated() {
+                        format!("{} -> {}", dep.pinned_version, dep.latest_version)
+                            .yellow()
+                            .to_string()
+                    } else {
+                        format!("{} (up to date)", dep.pinned_version)
+                            .green()
+                            .to_string()
+                    };
+
+                    writeln!(
+                        f,
+                        "- {} {} {}",
+                        dep.dependency.alias.bold(),
+                        dep.dependency.name,
+                        status
+                    )?;
+                }
+
+                if outdated.dependencies.iter().any(|dep| dep.is_outdated()) {
+                    writeln!(
+                        f,
+                        "\nTip: You can update the outdated dependencies by using: {}",
+                        "mvr update [name]".blue()
+                    )?;
+                }
+
+                writeln!(f, "\nChecked on network: {}", outdated.network.blue())
+            }
+            CommandOutput::Update(update) => {
+                if update.updated.is_empty() {
+                    return writeln!(
+                        f,
+                        "{}",
+                        "All version-pinned dependencies are already up to date.".italic()
+                    );
+                }
+
+                if let Some(diff) = &update.diff {
+                    writeln!(f, "{}", colorize_diff(diff))?;
+                }
+
+                let action = if update.dry_run {
+                    "Would update"
+                } else {
+                    "Updated"
+                };
+
+                for dep in update.updated.iter() {
+                    writeln!(
+                        f,
+                        "{} {}: {} -> {}",
+                        action,
+                        dep.alias.bold(),
+                        dep.from,
+                        dep.to.green()
+                    )?;
+                }
+
+                Ok(())
+            }
             CommandOutput::Resolve(package) => {
                 writeln!(f, "{}", serde_json::to_string_pretty(&package).unwrap())?;
                 Ok(())
             }
+            CommandOutput::Info(info) => {
+                let package = &info.package;
+                let description = package
+                    .metadata
+                    .get("description")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or("--");
+
+                writeln!(
+                    f,
+                    "\n{} {}",
+                    package.name.green().bold(),
+                    format!("(v{} on {})", package.version, info.network).dim()
+                )?;
+                writeln!(f, "# {}", description.italic())?;
+                writeln!(f, "Package address: {}", package.package_address)?;
+
+                for (key, label) in SEARCH_METADATA_KEYS {
+                    if let Some(value) = package.metadata.get(key).and_then(|v| v.as_str()) {
+                        writeln!(f, "{}: {}", label, value.blue())?;
+                    }
+                }
+
+                if let Some(package_info) = &package.package_info {
+                    writeln!(f, "\n{}", "Package info".bold())?;
+                    writeln!(f, "  Id:           {}", package_info.id)?;
+                    writeln!(
+                        f,
+                        "  Default name: {}",
+                        package_info.default_name.as_deref().unwrap_or("--")
+                    )?;
+                }
+
+                writeln!(f, "\n{}", "Versions".bold())?;
+                for version in info.versions.iter() {
+                    let git = match &version.git_info {
+                        Some(git_info) => format!(
+                            "{} @ {} ({})",
+                            git_info.repository_url.as_deref().unwrap_or("--"),
+                            git_info.tag.as_deref().unwrap_or("--"),
+                            git_info.path.as_deref().unwrap_or("--"),
+                        ),
+                        None => "no git info".italic().to_string(),
+                    };
+                    writeln!(
+                        f,
+                        "  v{:<4} {} {}",
+                        version.version, version.package_address, git
+                    )?;
+                }
+                if !info.missing_versions.is_empty() {
+                    let missing = info
+                        .missing_versions
+                        .iter()
+                        .map(|version| format!("v{}", version))
+                        .collect::<Vec<_>>();
+                    let message = format!("Could not query {}", missing.join(", "));
+                    writeln!(f, "  {}", message.yellow())?;
+                }
+
+                writeln!(f, "\n{}", package.name.bold())?;
+                let dependencies_title = format!("Dependencies ({})", info.dependencies.len());
+                writeln!(f, "├── {}", dependencies_title.blue())?;
+                write_tree_items(f, "│   ", info.dependencies.iter().cloned())?;
+
+                let dependents_title = format!("Top dependents ({} total)", info.total_dependents);
+                writeln!(f, "└── {}", dependents_title.blue())?;
+                write_tree_items(
+                    f,
+                    "    ",
+                    info.dependents
+                        .iter()
+                        .map(|d| format!("{} ({} calls)", d.package_id, d.aggregated_total_calls)),
+                )?;
+
+                if !info.analytics.is_empty() {
+                    let totals = info.analytics.iter().map(|a| a.total).collect::<Vec<_>>();
+                    writeln!(
+                        f,
+                        "\nCalls: {} {}",
+                        sparkline(&totals).green(),
+                        format!(
+                            "({} to {})",
+                            info.analytics.first().unwrap().date_from,
+                            info.analytics.last().unwrap().date_to
+                        )
+                        .dim()
+                    )?;
+                }
+
+                Ok(())
+            }
+            CommandOutput::Type(resolved) | CommandOutput::Struct(resolved) => {
+                for resolved_type in resolved.types.iter() {
+                    writeln!(f, "\n- {}", resolved_type.type_name.bold())?;
+
+                    match &resolved_type.type_tag {
+                        Some(type_tag) => writeln!(f, "  {}", type_tag.green())?,
+                        None => {
+                            let message = format!("Could not resolve type on {}", resolved.network);
+                            writeln!(f, "  {}", message.red())?
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            CommandOutput::Whois(resolved) => {
+                let width = resolved
+                    .names
+                    .iter()
+                    .map(|n| n.package_address.len())
+                    .max()
+                    .unwrap_or_default();
+
+                let header = format!("{:<width$}  Name ({})", "Package address", resolved.network);
+                writeln!(f, "{}", header.bold())?;
+
+                for name in resolved.names.iter() {
+                    let display_name = match &name.name {
+                        Some(name) => name.green().to_string(),
+                        None => "--".dim().to_string(),
+                    };
+                    writeln!(f, "{:<width$}  {}", name.package_address, display_name)?;
+                }
+
+                Ok(())
+            }
+            CommandOutput::Verify(verification) => {
+                writeln!(
+                    f,
+                    "\n{} {}",
+                    verification.name.green().bold(),
+                    format!("(v{} on {})", verification.version, verification.network).dim()
+                )?;
+                writeln!(f, "Package address: {}", verification.package_address)?;
+                writeln!(
+                    f,
+                    "Source:          {} @ {} ({})",
+                    verification.git.repository_url, verification.git.tag, verification.git.path
+                )?;
+
+                writeln!(f, "\n{}", "Modules".bold())?;
+                for module in verification.modules.iter() {
+                    let status = match module.status {
+                        ModuleStatus::Match => "matches".green().to_string(),
+                        ModuleStatus::Mismatch => "bytecode differs".red().to_string(),
+                        ModuleStatus::MissingFromSource => "missing from source".red().to_string(),
+                        ModuleStatus::MissingOnChain => "missing on-chain".red().to_string(),
+                    };
+                    writeln!(f, "  {:<24} {}", module.module, status)?;
+
+                    if module.status != ModuleStatus::Match {
+                        let digests = format!(
+                            "source: {}, on-chain: {}",
+                            module.source_digest.as_deref().unwrap_or("--"),
+                            module.on_chain_digest.as_deref().unwrap_or("--")
+                        );
+                        writeln!(f, "    {}", digests.dim())?;
+                    }
+                }
+
+                if verification.verified {
+                    writeln!(
+                        f,
+                        "\n{}",
+                        "The on-chain package matches its git source.".green()
+                    )
+                } else {
+                    writeln!(
+                        f,
+                        "\n{}",
+                        "The on-chain package does not match its git source.".red()
+                    )
+                }
+            }
             CommandOutput::Search(search_results) => {
                 for pkg in search_results.data.iter() {
                     let description = pkg
@@ -79,19 +696,29 @@ impl Display for CommandOutput {
                         .map(|s| s.to_string())
                         .unwrap_or("--".italic().to_string());
 
-                    let mut available_networks = vec![];
-
-                    if pkg.mainnet_package_info_id.is_some() {
-                        available_networks.push("mainnet".to_string());
-                    }
-
-                    if pkg.testnet_package_info_id.is_some() {
-                        available_networks.push("testnet".to_string());
-                    }
+                    let networks = [
+                        ("mainnet", &pkg.mainnet_package_info_id),
+                        ("testnet", &pkg.testnet_package_info_id),
+                    ]
+                    .iter()
+                    .map(|(network, package_info_id)| {
+                        if package_info_id.is_some() {
+                            format!("{} {}", network, "(linked)".green())
+                        } else {
+                            format!("{} {}", network, "(not linked)".dim())
+                        }
+                    })
+                    .collect::<Vec<_>>();
 
                     writeln!(f, "\n- {}", &pkg.name.green().bold())?;
                     writeln!(f, "# {}", description.italic())?;
-                    writeln!(f, "Networks: {}", available_networks.join(", "))?;
+                    writeln!(f, "Networks: {}", networks.join(", "))?;
+
+                    for (key, label) in SEARCH_METADATA_KEYS {
+                        if let Some(value) = pkg.metadata.get(key).and_then(|v| v.as_str()) {
+                            writeln!(f, "{}: {}", label, value.blue())?;
+                        }
+                    }
                 }
 
                 writeln!(
@@ -121,6 +748,159 @@ impl Display for CommandOutput {
 
                 Ok(())
             }
+            CommandOutput::Doctor(report) => {
+                for check in report.checks.iter() {
+                    let status = match check.status {
+                        CheckStatus::Pass => "pass".green(),
+                        CheckStatus::Warn => "warn".yellow(),
+                        CheckStatus::Fail => "fail".red(),
+                    };
+                    writeln!(f, "[{}] {}: {}", status, check.name.bold(), check.message)?;
+
+                    if let Some(fix) = &check.fix {
+                        let fix = format!("fix: {}", fix);
+                        writeln!(f, "       {}", fix.italic())?;
+                    }
+                }
+
+                if report.has_failures() {
+                    let summary =
+                        "Some checks failed. Include `mvr doctor --json` when reporting an issue.";
+                    writeln!(f, "\n{}", summary.red())
+                } else {
+                    writeln!(f, "\n{}", "Everything looks good!".green())
+                }
+            }
+            CommandOutput::Check(check) => {
+                writeln!(f, "Dependencies on {}:", check.network.bold())?;
+
+                write_tree_items(
+                    f,
+                    "",
+                    check.dependencies.iter().map(|dependency| {
+                        let package = match (&dependency.error, dependency.published_at) {
+                            (Some(error), _) => format!("{}", error.red()),
+                            (None, Some(published_at)) => format!("{}", published_at.dim()),
+                            (None, None) => format!("{}", "unknown package".italic()),
+                        };
+                        format!(
+                            "{} ({}) -> {}",
+                            dependency.alias.bold(),
+                            dependency.source,
+                            package
+                        )
+                    }),
+                )?;
+
+                if check.conflicts.is_empty() {
+                    return writeln!(f, "\n{}", "No conflicting dependencies found.".green());
+                }
+
+                writeln!(f, "\n{}", "Conflicting dependencies:".red())?;
+
+                for conflict in &check.conflicts {
+                    writeln!(f, "  - {}", conflict.message)?;
+                }
+
+                Ok(())
+            }
+            CommandOutput::CacheList(cached) => {
+                if cached.entries.is_empty() {
+                    return writeln!(f, "{}", "No cached resolutions.".italic());
+                }
+
+                let now = now();
+                let width = cached
+                    .entries
+                    .iter()
+                    .map(|e| e.key.len())
+                    .max()
+                    .unwrap_or(0);
+                let mut network = None;
+
+                for entry in &cached.entries {
+                    if network != Some(&entry.network) {
+                        writeln!(f, "\n{}", entry.network.bold())?;
+                        network = Some(&entry.network);
+                    }
+
+                    let arrow = match entry.kind {
+                        CachedResolutionKind::Resolution => "->",
+                        CachedResolutionKind::ReverseResolution => "<-",
+                    };
+                    let value = entry
+                        .value
+                        .clone()
+                        .unwrap_or_else(|| "no name".italic().to_string());
+
+                    let expiry = match entry.expires_at {
+                        None => "pinned".to_string(),
+                        Some(expires_at) if expires_at <= now => "expired".to_string(),
+                        Some(expires_at) => {
+                            format!("expires in {}", format_duration(expires_at - now))
+                        }
+                    };
+
+                    writeln!(
+                        f,
+                        "  {:<width$} {} {} {}",
+                        entry.key,
+                        arrow,
+                        value.green(),
+                        format!("({})", expiry).dim()
+                    )?;
+                }
+
+                let hint = format!(
+                    "Pinned names are cached indefinitely, other results for {} (MVR_CACHE_TTL_SECS).",
+                    format_duration(cached.ttl_secs)
+                );
+                writeln!(f, "\n{}", hint.italic())
+            }
+            CommandOutput::CacheClear(cleared) => {
+                let message = if cleared.all {
+                    format!(
+                        "Removed the local cache ({} cached resolutions).",
+                        cleared.removed
+                    )
+                } else {
+                    format!("Removed {} cached resolutions.", cleared.removed)
+                };
+                writeln!(f, "{}", message.green())
+            }
         }
     }
 }
+
+/// Formats a number of seconds as e.g. `4m 12s`.
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Writes a list of items as the leaves of a tree, under the given prefix.
+fn write_tree_items(
+    f: &mut Formatter<'_>,
+    prefix: &str,
+    items: impl ExactSizeIterator<Item = String>,
+) -> fmt::Result {
+    let len = items.len();
+
+    if len == 0 {
+        return writeln!(f, "{}└── {}", prefix, "none".italic());
+    }
+
+    for (i, item) in items.enumerate() {
+        let branch = if i + 1 == len {
+            "└──"
+        } else {
+            "├──"
+        };
+        writeln!(f, "{}{} {}", prefix, branch, item)?;
+    }
+
+    Ok(())
+}
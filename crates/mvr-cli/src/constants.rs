@@ -1,15 +1,39 @@
 use std::fmt::{self, Display, Formatter};
 
-pub const MINIMUM_BUILD_SUI_VERSION: (u32, u32) = (1, 63);
+use semver::Version;
+
+pub const MINIMUM_BUILD_SUI_VERSION: Version = Version::new(1, 63, 0);
 
 pub enum EnvVariables {
     SuiBinaryPath,
+    MvrOffline,
+    MvrCacheDir,
+    MvrCacheTtlSecs,
+    MvrUpdateLock,
+    MvrConfig,
+    MvrWorkspace,
+    MvrConnectTimeoutSecs,
+    MvrRequestTimeoutSecs,
+    MvrMaxRetries,
+    MvrFallbackNetwork,
+    MvrSuiRecordFile,
 }
 
 impl Display for EnvVariables {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             EnvVariables::SuiBinaryPath => write!(f, "SUI_BINARY_PATH"),
+            EnvVariables::MvrOffline => write!(f, "MVR_OFFLINE"),
+            EnvVariables::MvrCacheDir => write!(f, "MVR_CACHE_DIR"),
+            EnvVariables::MvrCacheTtlSecs => write!(f, "MVR_CACHE_TTL_SECS"),
+            EnvVariables::MvrUpdateLock => write!(f, "MVR_UPDATE_LOCK"),
+            EnvVariables::MvrConfig => write!(f, "MVR_CONFIG"),
+            EnvVariables::MvrWorkspace => write!(f, "MVR_WORKSPACE"),
+            EnvVariables::MvrConnectTimeoutSecs => write!(f, "MVR_CONNECT_TIMEOUT_SECS"),
+            EnvVariables::MvrRequestTimeoutSecs => write!(f, "MVR_REQUEST_TIMEOUT_SECS"),
+            EnvVariables::MvrMaxRetries => write!(f, "MVR_MAX_RETRIES"),
+            EnvVariables::MvrFallbackNetwork => write!(f, "MVR_FALLBACK_NETWORK"),
+            EnvVariables::MvrSuiRecordFile => write!(f, "MVR_SUI_RECORD_FILE"),
         }
     }
 }
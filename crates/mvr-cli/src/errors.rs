@@ -1,5 +1,11 @@
+use std::collections::BTreeMap;
+
+use mvr_types::errors::{MoveRegistryError, NameServiceError};
+use serde::Serialize;
 use thiserror::Error;
 
+use crate::utils::config::config_path_display;
+
 #[derive(Debug, Error, Clone, Hash, Eq, PartialEq)]
 pub enum CliError {
     #[error("There was an error querying the MVR API: {0}. You can either try again, or try installing the latest version of the CLI.")]
@@ -11,19 +17,182 @@ pub enum CliError {
     #[error("The requested package {0}, on network {1}, either does not exist, or it does not have an on-chain metadata mapping defined.")]
     NameNotExists(String, String),
 
-    #[error("The requested network (or chain identifier) is not supported. Only `mainnet` and `testnet` are supported. 
-    If you are using this locally, you can set the `MVR_FALLBACK_NETWORK` environment variable to `mainnet` or `testnet`.")]
+    #[error("The requested network (or chain identifier) is not supported. Only `mainnet`, `testnet` and networks configured in `{config}` are supported. 
+    If you are using this locally, you can set the `MVR_FALLBACK_NETWORK` environment variable to `mainnet`, `testnet` or a configured network.", config = config_path_display())]
     NetworkNotSupported,
 
-    #[error("Invalid network: {0}. Please use `mainnet` or `testnet`.")]
+    #[error("Invalid network: {0}. Please use `mainnet`, `testnet` or a network configured in `{config}`.", config = config_path_display())]
     InvalidNetwork(String),
 
+    #[error("The network {0} does not have a `{1}` configured. Set it under `[networks.{0}]` in `{config}`, or with the `MVR_NETWORK_<NAME>_{2}` environment variable.", config = config_path_display())]
+    MissingNetworkConfig(String, String, String),
+
+    #[error("Failed to load the MVR config: {0}")]
+    InvalidConfig(String),
+
     #[error("\n*** Failed to find the SUI binary. *** \nPlease make sure it is installed and available in your PATH, or supply it using {0} environment variable.\n")]
     SuiBinaryNotFound(String),
 
+    #[error("Could not find an MVR dependency matching {0} in your Move.toml.")]
+    DependencyNotFound(String),
+
+    #[error("Several MVR dependencies match {0} in your Move.toml: {1}. Pass the alias of the one to remove instead.")]
+    AmbiguousDependency(String, String),
+
+    #[error("The package {0}, on network {1}, is not available in the offline cache. Run the same command without `--offline` (or the `MVR_OFFLINE` environment variable) once, to populate the cache.")]
+    NotCachedOffline(String, String),
+
+    #[error("The MVR API does not support the {0} endpoint. Please make sure it is up to date.")]
+    EndpointNotSupported(String),
+
+    #[error("Move.toml not found at {0}.")]
+    MoveTomlNotFound(String),
+
+    #[error("Sui CLI version {found} is too low. Please upgrade to at least {required} in order to build your code using mvr.")]
+    SuiVersionTooLow { found: String, required: String },
+
+    #[error("Could not detect the version of the Sui CLI from its output: `{0}`.")]
+    InvalidSuiVersion(String),
+
+    #[error("A different dependency named {0} already exists in {1}. Use `--as` to add it under another name, or `--force` to overwrite it.")]
+    DependencyAlreadyExists(String, String),
+
+    #[error(
+        "{1} already depends on the packages being added:\n{0}\nUse `--force` to add them anyway."
+    )]
+    DependencyConflicts(String, String),
+
     #[error("Missing Move.lock file for dependency {0}.")]
     MissingLockFile(String),
 
     #[error("Missing Move.toml file for dependency {0}.")]
     MissingTomlFile(String),
 }
+
+impl CliError {
+    /// A stable, machine-readable code for the error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CliError::Querying(_) => "querying_failed",
+            CliError::UnexpectedParsing(_) => "unexpected_parsing",
+            CliError::NameNotExists(..) => "name_not_found",
+            CliError::NetworkNotSupported => "network_not_supported",
+            CliError::InvalidNetwork(_) => "invalid_network",
+            CliError::MissingNetworkConfig(..) => "missing_network_config",
+            CliError::InvalidConfig(_) => "invalid_config",
+            CliError::SuiBinaryNotFound(_) => "sui_binary_not_found",
+            CliError::DependencyNotFound(_) => "dependency_not_found",
+            CliError::AmbiguousDependency(..) => "ambiguous_dependency",
+            CliError::NotCachedOffline(..) => "not_cached_offline",
+            CliError::EndpointNotSupported(_) => "endpoint_not_supported",
+            CliError::MoveTomlNotFound(_) => "move_toml_not_found",
+            CliError::SuiVersionTooLow { .. } => "sui_version_too_low",
+            CliError::InvalidSuiVersion(_) => "invalid_sui_version",
+            CliError::DependencyAlreadyExists(..) => "dependency_already_exists",
+            CliError::DependencyConflicts(..) => "dependency_conflicts",
+            CliError::MissingLockFile(_) => "missing_lock_file",
+            CliError::MissingTomlFile(_) => "missing_toml_file",
+        }
+    }
+
+    /// The values (name, network, etc.) the error refers to.
+    pub fn context(&self) -> BTreeMap<&'static str, String> {
+        let context: Vec<(&'static str, &String)> = match self {
+            CliError::NameNotExists(name, network) | CliError::NotCachedOffline(name, network) => {
+                vec![("name", name), ("network", network)]
+            }
+            CliError::InvalidNetwork(network) => vec![("network", network)],
+            CliError::MissingNetworkConfig(network, field, _) => {
+                vec![("network", network), ("field", field)]
+            }
+            CliError::SuiBinaryNotFound(variable) => vec![("env_variable", variable)],
+            CliError::DependencyNotFound(name) => vec![("name", name)],
+            CliError::AmbiguousDependency(name, aliases) => {
+                vec![("name", name), ("aliases", aliases)]
+            }
+            CliError::EndpointNotSupported(url) => vec![("url", url)],
+            CliError::MoveTomlNotFound(path) => vec![("path", path)],
+            CliError::DependencyAlreadyExists(alias, path) => {
+                vec![("alias", alias), ("path", path)]
+            }
+            CliError::DependencyConflicts(conflicts, path) => {
+                vec![("conflicts", conflicts), ("path", path)]
+            }
+            CliError::SuiVersionTooLow { found, required } => {
+                vec![("found", found), ("required", required)]
+            }
+            CliError::InvalidSuiVersion(output) => vec![("output", output)],
+            CliError::MissingLockFile(dependency) | CliError::MissingTomlFile(dependency) => {
+                vec![("dependency", dependency)]
+            }
+            CliError::Querying(_)
+            | CliError::UnexpectedParsing(_)
+            | CliError::NetworkNotSupported
+            | CliError::InvalidConfig(_) => vec![],
+        };
+
+        context.into_iter().map(|(k, v)| (k, v.clone())).collect()
+    }
+}
+
+/// The JSON representation of a failed command, printed when `--json` is passed.
+#[derive(Debug, Serialize)]
+pub struct ErrorEnvelope {
+    pub error: ErrorBody,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    /// A stable, machine-readable code for the error (`unknown` for unclassified errors).
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub context: BTreeMap<&'static str, String>,
+}
+
+impl From<&anyhow::Error> for ErrorEnvelope {
+    fn from(error: &anyhow::Error) -> Self {
+        let (code, context) = error
+            .chain()
+            .find_map(|cause| {
+                if let Some(e) = cause.downcast_ref::<CliError>() {
+                    return Some((e.code(), e.context()));
+                }
+
+                if let Some(e) = cause.downcast_ref::<MoveRegistryError>() {
+                    return Some(move_registry_error_details(e));
+                }
+
+                cause
+                    .downcast_ref::<NameServiceError>()
+                    .map(|_| ("invalid_name", BTreeMap::new()))
+            })
+            .unwrap_or(("unknown", BTreeMap::new()));
+
+        Self {
+            error: ErrorBody {
+                code,
+                message: format!("{:#}", error).trim().to_string(),
+                context,
+            },
+        }
+    }
+}
+
+fn move_registry_error_details(
+    error: &MoveRegistryError,
+) -> (&'static str, BTreeMap<&'static str, String>) {
+    match error {
+        MoveRegistryError::InvalidName(name) => {
+            ("invalid_name", BTreeMap::from([("name", name.clone())]))
+        }
+        MoveRegistryError::InvalidType(type_name) => (
+            "invalid_type",
+            BTreeMap::from([("type", type_name.clone())]),
+        ),
+        MoveRegistryError::NameNotFound(name) => {
+            ("name_not_found", BTreeMap::from([("name", name.clone())]))
+        }
+        MoveRegistryError::InvalidVersion => ("invalid_version", BTreeMap::new()),
+    }
+}
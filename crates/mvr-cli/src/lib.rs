@@ -5,69 +5,549 @@ pub mod types;
 pub mod utils;
 
 use crate::constants::MINIMUM_BUILD_SUI_VERSION;
-use crate::types::api_data::{query_multiple_dependencies, query_package, search_names};
-use crate::utils::sui_binary::{cache_package, check_sui_version};
+use crate::errors::CliError;
+use crate::types::api_data::{
+    query_dependencies, query_dependents, query_multiple_dependencies, query_name_analytics,
+    query_package, query_package_modules, query_version_history, resolve_struct_definitions,
+    resolve_type_tags, reverse_resolve_addresses, search_names,
+};
+use crate::types::api_types::SafeGitInfo;
+use crate::utils::conflicts::{check_dependencies, find_conflicts};
+use crate::utils::doctor::run_checks;
+use crate::utils::package_cache::PackageCache;
+use crate::utils::resolution_cache::ResolutionCache;
+use crate::utils::sui_binary::{
+    build_package_modules, cache_package, check_sui_version, SuiCachePackageResponse,
+};
+use crate::utils::verify::{checkout_git_source, compare_modules};
 
 use commands::CommandOutput;
 use types::Network;
 
-use types::MoveRegistryDependencies;
+use types::{
+    AddOptions, CachedResolutions, CheckedDependency, ClearedCache, DependencyCheck,
+    ListedDependencies, ListedDependency, ModuleStatus, MoveRegistryDependencies, MvrDependency,
+    NameInfo, OutdatedDependencies, PackageVerification, PinnedDependency, RemovedDependency,
+    ResolvedType, ResolvedTypes, ReverseResolvedName, ReverseResolvedNames, UpdatedDependencies,
+    UpdatedDependency, WriteOptions,
+};
+use utils::diff::colorize_diff;
 use utils::manifest::MoveToml;
 use utils::sui_binary::get_active_network;
+use utils::workspace::{PinConflict, Workspace};
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose, Engine};
+use mvr_types::api::MAX_PAGE_LIMIT;
+use mvr_types::name::VersionedName;
+use mvr_types::name_service::Domain;
+use sui_sdk_types::Address;
 use yansi::Paint;
 
 const TESTNET_CHAIN_ID: &str = "4c78adac";
 const MAINNET_CHAIN_ID: &str = "35834a8a";
 
-async fn update_mvr_packages(
-    mut move_toml: MoveToml,
-    package_name: &str,
+/// Resolves (and caches) MVR names, and adds them as dependencies to every given Move.toml file.
+///
+/// Every Move.toml file is checked for conflicting dependencies before any of them is saved.
+/// In dry-run mode, nothing is saved, and the output contains the diff of every Move.toml instead.
+pub async fn update_mvr_packages(
+    move_tomls: Vec<MoveToml>,
+    package_names: &[String],
     network: &Network,
+    options: &AddOptions,
 ) -> Result<String> {
+    if package_names.len() > 1 && (options.alias.is_some() || options.version.is_some()) {
+        bail!("`--as` and `--version` can only be used when adding a single dependency.");
+    }
+
+    let package_names = package_names
+        .iter()
+        .map(|name| {
+            let name = VersionedName::from_str(name)?;
+
+            match (name.version, options.version) {
+                (Some(pinned), Some(version)) if pinned != version => bail!(
+                    "{} is already pinned to version {}, which conflicts with `--version {}`.",
+                    name,
+                    pinned,
+                    version
+                ),
+                _ => Ok(VersionedName {
+                    version: name.version.or(options.version),
+                    ..name
+                }
+                .to_string()),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
     let dependencies = MoveRegistryDependencies {
-        packages: vec![package_name.to_string()],
+        packages: package_names.clone(),
     };
 
-    let resolved_packages = query_multiple_dependencies(dependencies.clone(), network).await?;
+    let resolved_packages = query_multiple_dependencies(dependencies, network, false).await?;
 
-    let package_request = resolved_packages
-        .get(package_name)
-        .expect("[mvr invariant] Package must exist in resolved packages.");
+    let mut added = vec![];
 
-    let cached_package = cache_package(package_request.clone(), network)?;
+    for package_name in &package_names {
+        let package_request = resolved_packages
+            .get(package_name)
+            .expect("[mvr invariant] Package must exist in resolved packages.");
 
-    move_toml.add_dependency(&cached_package.name, package_name)?;
+        let cached_package = cache_package(package_request.clone(), network)?;
 
-    move_toml.save_to_file()?;
+        added.push((
+            MvrDependency {
+                alias: options
+                    .alias
+                    .clone()
+                    .unwrap_or_else(|| cached_package.name.clone()),
+                name: package_name.clone(),
+                network: options.network.as_ref().map(|network| network.to_string()),
+            },
+            cached_package,
+        ));
+    }
 
-    let output_msg = format!(
-        "{}\nYou can use this dependency in your modules by calling: {}",
-        &format!(
-            "\nSuccessfully added dependency {} to your Move.toml\n",
-            package_name.green()
-        ),
-        &format!("use {}::<module>;\n", cached_package.name).green()
-    );
+    if !options.force {
+        for move_toml in &move_tomls {
+            check_added_dependencies(move_toml, &added, network).await?;
+        }
+    }
+
+    let mut move_tomls = move_tomls;
+
+    for move_toml in &mut move_tomls {
+        for (dependency, _) in &added {
+            move_toml.add_dependency(dependency, options.force)?;
+        }
+    }
+
+    let mut output_msg = String::new();
+
+    for move_toml in &move_tomls {
+        let diff = move_toml.write_changes(&options.write)?;
+
+        if let Some(diff) = diff.filter(|_| options.write.dry_run) {
+            output_msg.push_str(&format!("{}\n", colorize_diff(&diff)));
+        }
+    }
+
+    for (dependency, cached_package) in &added {
+        if options.write.dry_run {
+            output_msg.push_str(&format!(
+                "\nWould add dependency {} to your Move.toml\n",
+                dependency.name.green()
+            ));
+            continue;
+        }
+
+        output_msg.push_str(&format!(
+            "{}\nYou can use this dependency in your modules by calling: {}",
+            &format!(
+                "\nSuccessfully added dependency {} to your Move.toml\n",
+                dependency.name.green()
+            ),
+            &format!("use {}::<module>;\n", cached_package.name).green()
+        ));
+    }
 
     Ok(output_msg)
 }
 
-pub async fn subcommand_add_dependency(package_name: &str) -> Result<CommandOutput> {
-    check_sui_version(MINIMUM_BUILD_SUI_VERSION)?;
+/// Fails if the dependencies being added point to the same on-chain package as an existing
+/// dependency (or each other), or to the address of a named address of the Move.toml file.
+async fn check_added_dependencies(
+    move_toml: &MoveToml,
+    added: &[(MvrDependency, SuiCachePackageResponse)],
+    network: &Network,
+) -> Result<()> {
+    let new_aliases = added
+        .iter()
+        .map(|(dependency, _)| dependency.alias.clone())
+        .collect::<BTreeSet<_>>();
+
+    let mut dependencies = check_dependencies(move_toml, network)
+        .await
+        .into_iter()
+        .filter(|dependency| !new_aliases.contains(&dependency.alias))
+        .collect::<Vec<_>>();
+
+    dependencies.extend(
+        added
+            .iter()
+            .map(|(dependency, cached_package)| CheckedDependency {
+                alias: dependency.alias.clone(),
+                source: dependency.name.clone(),
+                original_id: Some(cached_package.original_id),
+                published_at: Some(cached_package.published_at),
+                error: None,
+            }),
+    );
+
+    let conflicts = find_conflicts(&dependencies, &move_toml.get_addresses())
+        .into_iter()
+        .filter(|conflict| {
+            conflict
+                .dependencies
+                .iter()
+                .any(|alias| new_aliases.contains(alias))
+        })
+        .map(|conflict| format!("  - {}", conflict.message))
+        .collect::<Vec<_>>();
+
+    if !conflicts.is_empty() {
+        bail!(CliError::DependencyConflicts(
+            conflicts.join("\n"),
+            move_toml
+                .path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or("Move.toml".to_string())
+        ));
+    }
+
+    Ok(())
+}
+
+/// Adds MVR dependencies to the Move.toml file in the current directory, or to every
+/// Move.toml file of a workspace (when a workspace root is given).
+pub async fn subcommand_add_dependency(
+    package_names: &[String],
+    options: AddOptions,
+    workspace: Option<PathBuf>,
+) -> Result<CommandOutput> {
+    check_sui_version(&MINIMUM_BUILD_SUI_VERSION)?;
+
+    let network = match &options.network {
+        Some(network) => network.clone(),
+        None => get_active_network()?,
+    };
+
+    let Some(workspace_root) = workspace else {
+        let move_toml = MoveToml::new(
+            env::current_dir()
+                .context("Failed to get current directory")?
+                .join("Move.toml"),
+        )?;
+
+        let cmd_output =
+            update_mvr_packages(vec![move_toml], package_names, &network, &options).await?;
+
+        return Ok(CommandOutput::Add(cmd_output));
+    };
+
+    let workspace = Workspace::discover(&workspace_root)?;
+    let paths = workspace
+        .packages
+        .iter()
+        .filter_map(|package| package.path.clone())
+        .collect::<Vec<_>>();
+
+    let mut cmd_output =
+        update_mvr_packages(workspace.packages, package_names, &network, &options).await?;
+
+    cmd_output.push_str(&format!(
+        "\n{} {} packages in the workspace:\n",
+        if options.write.dry_run {
+            "Would update"
+        } else {
+            "Updated"
+        },
+        paths.len()
+    ));
+    for path in &paths {
+        cmd_output.push_str(&format!("  - {}\n", path.display()));
+    }
+
+    // Re-discover the workspace, to check the pins including the new dependency.
+    cmd_output.push_str(&format_pin_conflicts(
+        &Workspace::discover(&workspace_root)?.pin_conflicts(),
+    ));
+
+    Ok(CommandOutput::Add(cmd_output))
+}
+
+/// Formats the conflicting pins of a workspace as warnings (empty if there are none).
+pub fn format_pin_conflicts(conflicts: &[PinConflict]) -> String {
+    let mut output = String::new();
+
+    for conflict in conflicts {
+        output.push_str(&format!(
+            "\n{} {} {}\n",
+            "Warning:".yellow().bold(),
+            conflict.name.bold(),
+            "is requested with conflicting versions across the workspace:".yellow()
+        ));
+
+        for (path, name) in &conflict.pins {
+            output.push_str(&format!("  - {} in {}\n", name, path.display()));
+        }
+    }
+
+    output
+}
+
+/// Removes an MVR dependency from the Move.toml file in the current directory.
+/// The dependency can be referenced either by its alias (the key in `[dependencies]`) or by its MVR name.
+/// In dry-run mode, the Move.toml is not modified, and the diff of the changes is returned instead.
+pub fn subcommand_remove_dependency(name: &str, write: WriteOptions) -> Result<CommandOutput> {
+    let mut move_toml = MoveToml::new(
+        env::current_dir()
+            .context("Failed to get current directory")?
+            .join("Move.toml"),
+    )?;
+
+    let Some(removed) = move_toml.remove_dependency(name)? else {
+        bail!(CliError::DependencyNotFound(name.to_string()));
+    };
+
+    let diff = move_toml.write_changes(&write)?;
+
+    Ok(CommandOutput::Remove(RemovedDependency {
+        dependency: removed,
+        dry_run: write.dry_run,
+        diff: diff.filter(|_| write.dry_run),
+    }))
+}
+
+/// Lists all MVR dependencies of the Move.toml file in the current directory.
+/// When the dependencies can be resolved (the network is known and the API is reachable),
+/// the resolved package address and git information are included for each dependency.
+pub async fn subcommand_list_dependencies(network: Option<Network>) -> Result<CommandOutput> {
     let move_toml = MoveToml::new(
         env::current_dir()
             .context("Failed to get current directory")?
             .join("Move.toml"),
     )?;
 
-    let network = get_active_network()?;
-    let cmd_output = update_mvr_packages(move_toml, package_name, &network).await?;
+    let dependencies = move_toml.get_mvr_dependencies();
 
-    Ok(CommandOutput::Add(cmd_output))
+    let network = match network {
+        Some(network) => Some(network),
+        None => get_active_network()
+            .inspect_err(|e| {
+                eprintln!(
+                    "{} {}",
+                    "[mvr] Could not detect the active network, skipping resolution:".yellow(),
+                    e
+                )
+            })
+            .ok(),
+    };
+
+    let mut resolved_packages = HashMap::new();
+
+    if let Some(network) = &network {
+        if !dependencies.is_empty() {
+            let packages = MoveRegistryDependencies {
+                packages: dependencies.iter().map(|d| d.name.clone()).collect(),
+            };
+
+            match query_multiple_dependencies(packages, network, false).await {
+                Ok(resolved) => resolved_packages = resolved,
+                Err(e) => eprintln!("{} {}", "[mvr] Failed to resolve dependencies:".yellow(), e),
+            }
+        }
+    }
+
+    let dependencies = dependencies
+        .into_iter()
+        .map(|dependency| {
+            let pinned_version = VersionedName::from_str(&dependency.name)
+                .ok()
+                .and_then(|name| name.version);
+            let resolved = resolved_packages.get(&dependency.name);
+
+            ListedDependency {
+                pinned_version,
+                resolved_version: resolved.map(|pkg| pkg.version),
+                package_address: resolved.map(|pkg| pkg.package_address.clone()),
+                git_info: resolved.and_then(|pkg| pkg.git_info.clone()),
+                dependency,
+            }
+        })
+        .collect();
+
+    Ok(CommandOutput::List(ListedDependencies {
+        network,
+        dependencies,
+    }))
+}
+
+/// Lists all version-pinned MVR dependencies of the Move.toml file in the current directory,
+/// along with the latest version available for each of them.
+pub async fn subcommand_outdated_dependencies(network: Option<Network>) -> Result<CommandOutput> {
+    let move_toml = MoveToml::new(
+        env::current_dir()
+            .context("Failed to get current directory")?
+            .join("Move.toml"),
+    )?;
+
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+
+    let dependencies = get_pinned_dependencies(&move_toml, None, &network).await?;
+
+    Ok(CommandOutput::Outdated(OutdatedDependencies {
+        network,
+        dependencies,
+    }))
+}
+
+/// Rewrites the version pins of the MVR dependencies of the Move.toml file in the current directory
+/// to their latest versions (or to `max_version`, if it is lower).
+///
+/// If `name` is supplied, only the dependency matching that name (or alias) is updated.
+/// In dry-run mode, the Move.toml is not modified, and the diff of the changes is returned instead.
+pub async fn subcommand_update_dependencies(
+    name: Option<String>,
+    network: Option<Network>,
+    max_version: Option<u64>,
+    write: WriteOptions,
+) -> Result<CommandOutput> {
+    let mut move_toml = MoveToml::new(
+        env::current_dir()
+            .context("Failed to get current directory")?
+            .join("Move.toml"),
+    )?;
+
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+
+    let updated =
+        update_pinned_dependencies(&mut move_toml, name.as_deref(), &network, max_version).await?;
+    let diff = move_toml.write_changes(&write)?;
+
+    Ok(CommandOutput::Update(UpdatedDependencies {
+        network,
+        updated,
+        dry_run: write.dry_run,
+        diff: diff.filter(|_| write.dry_run),
+    }))
+}
+
+/// Rewrites the version pins of the MVR dependencies of a `Move.toml` (optionally filtered by name
+/// or alias) to their latest versions on the given network (or to `max_version`, if it is lower).
+///
+/// Only the names are rewritten in place, so the rest of each dependency is kept as is. The changes
+/// are not written to disk.
+pub async fn update_pinned_dependencies(
+    move_toml: &mut MoveToml,
+    name: Option<&str>,
+    network: &Network,
+    max_version: Option<u64>,
+) -> Result<Vec<UpdatedDependency>> {
+    let pinned = get_pinned_dependencies(move_toml, name, network).await?;
+
+    let mut updated = vec![];
+
+    for dep in pinned {
+        let target_version =
+            max_version.map_or(dep.latest_version, |max| max.min(dep.latest_version));
+
+        if target_version <= dep.pinned_version {
+            continue;
+        }
+
+        let mut versioned_name = VersionedName::from_str(&dep.dependency.name)?;
+        versioned_name.version = Some(target_version);
+        let new_name = versioned_name.to_string();
+
+        move_toml.set_dependency_name(&dep.dependency.alias, &new_name)?;
+
+        updated.push(UpdatedDependency {
+            alias: dep.dependency.alias,
+            from: dep.dependency.name,
+            to: new_name,
+        });
+    }
+
+    Ok(updated)
+}
+
+/// Finds all version-pinned MVR dependencies of a `Move.toml` (optionally filtered by name or alias),
+/// and queries the latest version of each of them on the given network (bypassing the resolution
+/// cache).
+pub async fn get_pinned_dependencies(
+    move_toml: &MoveToml,
+    name: Option<&str>,
+    network: &Network,
+) -> Result<Vec<PinnedDependency>> {
+    let requested_name = name.and_then(|name| VersionedName::from_str(name).ok());
+
+    let dependencies = move_toml
+        .get_mvr_dependencies()
+        .into_iter()
+        .filter(|dep| {
+            let Some(name) = name else {
+                return true;
+            };
+
+            dep.alias == name
+                || dep.name == name
+                || requested_name.as_ref().is_some_and(|requested| {
+                    VersionedName::from_str(&dep.name)
+                        .is_ok_and(|dep_name| dep_name.name == requested.name)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(name) = name {
+        if dependencies.is_empty() {
+            bail!(CliError::DependencyNotFound(name.to_string()));
+        }
+    }
+
+    // We only care about dependencies pinned to a specific version,
+    // as unversioned dependencies always resolve to the latest version.
+    let pinned = dependencies
+        .into_iter()
+        .filter_map(|dep| {
+            let versioned_name = VersionedName::from_str(&dep.name).ok()?;
+            let version = versioned_name.version?;
+            Some((dep, versioned_name.name.to_string(), version))
+        })
+        .collect::<Vec<_>>();
+
+    if pinned.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let latest_packages = query_multiple_dependencies(
+        MoveRegistryDependencies {
+            packages: pinned
+                .iter()
+                .map(|(_, name, _)| name.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        },
+        network,
+        // The latest versions are always queried from the API, as cached ones may be outdated.
+        true,
+    )
+    .await?;
+
+    Ok(pinned
+        .into_iter()
+        .map(|(dependency, name, pinned_version)| PinnedDependency {
+            dependency,
+            pinned_version,
+            // SAFETY: `query_multiple_dependencies` errors if any of the packages is not resolved.
+            latest_version: latest_packages[&name].version,
+        })
+        .collect())
 }
 
 /// resolve a .move name to an address. E.g., `nft@sample` => 0x... cf. subcommand_list implementation.
@@ -81,12 +561,325 @@ pub async fn subcommand_resolve_name(
     Ok(CommandOutput::Resolve(package_mainnet.1))
 }
 
+/// Collects the metadata, version history, dependencies, top dependents and analytics of a name.
+pub async fn subcommand_name_info(
+    name: &str,
+    network: Option<Network>,
+    dependents_limit: u32,
+) -> Result<CommandOutput> {
+    // The API returns at most `MAX_PAGE_LIMIT` dependents at once.
+    if !(1..=MAX_PAGE_LIMIT).contains(&dependents_limit) {
+        bail!(
+            "`--dependents` must be between 1 and {}, got {}.",
+            MAX_PAGE_LIMIT,
+            dependents_limit
+        );
+    }
+
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+    let versioned_name = VersionedName::from_str(name)?;
+
+    let (_, package) = query_package(name, &network).await?;
+
+    // The version history always goes up to the latest version, even if a specific one was requested.
+    let latest_version = match versioned_name.version {
+        Some(_) => {
+            let unversioned = VersionedName {
+                version: None,
+                ..versioned_name.clone()
+            };
+            query_package(&unversioned.to_string(), &network)
+                .await?
+                .1
+                .version
+        }
+        None => package.version,
+    };
+
+    let ((versions, missing_versions), dependencies, dependents, analytics) = futures::try_join!(
+        query_version_history(&versioned_name, latest_version, &network),
+        query_dependencies(&package.package_address, &network),
+        query_dependents(&package.package_address, dependents_limit, &network),
+        query_name_analytics(&versioned_name, &network),
+    )?;
+
+    Ok(CommandOutput::Info(NameInfo {
+        network,
+        package,
+        versions,
+        missing_versions,
+        dependencies: dependencies
+            .dependencies
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        dependents: dependents.data,
+        total_dependents: analytics.total_dependents,
+        analytics: analytics.analytics,
+    }))
+}
+
+/// Resolves types with MVR names to their canonical type tags (`mvr type`).
+pub async fn subcommand_resolve_types(
+    types: Vec<String>,
+    file: Option<PathBuf>,
+    network: Option<Network>,
+) -> Result<CommandOutput> {
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+    let types = collect_types(types, file)?;
+    let resolution = resolve_type_tags(&types, &network).await?;
+
+    Ok(CommandOutput::Type(into_resolved_types(
+        types, resolution, network,
+    )))
+}
+
+/// Resolves types with MVR names to their defining-ID struct tags (`mvr struct`).
+pub async fn subcommand_resolve_structs(
+    types: Vec<String>,
+    file: Option<PathBuf>,
+    network: Option<Network>,
+) -> Result<CommandOutput> {
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+    let types = collect_types(types, file)?;
+    let resolution = resolve_struct_definitions(&types, &network).await?;
+
+    Ok(CommandOutput::Struct(into_resolved_types(
+        types, resolution, network,
+    )))
+}
+
+/// Combines the types passed as arguments with the ones in `file` (one type per line,
+/// empty lines and lines starting with `#` are ignored), removing duplicates.
+fn collect_types(types: Vec<String>, file: Option<PathBuf>) -> Result<Vec<String>> {
+    let mut all_types = types;
+
+    if let Some(file) = file {
+        let content = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read types from {}", file.display()))?;
+
+        all_types.extend(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string),
+        );
+    }
+
+    let mut seen = BTreeSet::new();
+    all_types.retain(|t| seen.insert(t.clone()));
+
+    if all_types.is_empty() {
+        bail!("No types were provided. Pass them as arguments, or use `--file <path>`.");
+    }
+
+    Ok(all_types)
+}
+
+fn into_resolved_types(
+    types: Vec<String>,
+    mut resolution: HashMap<String, Option<String>>,
+    network: Network,
+) -> ResolvedTypes {
+    ResolvedTypes {
+        network,
+        types: types
+            .into_iter()
+            .map(|type_name| ResolvedType {
+                type_tag: resolution.remove(&type_name).flatten(),
+                type_name,
+            })
+            .collect(),
+    }
+}
+
+/// Resolves package addresses to their MVR names (`mvr whois`). If no addresses are given,
+/// they are read from stdin (separated by whitespace or newlines).
+pub async fn subcommand_whois(
+    addresses: Vec<String>,
+    network: Option<Network>,
+) -> Result<CommandOutput> {
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+
+    let addresses = if addresses.is_empty() {
+        std::io::read_to_string(std::io::stdin())?
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    } else {
+        addresses
+    };
+
+    let mut seen = BTreeSet::new();
+    let addresses = addresses
+        .iter()
+        .map(|address| {
+            Address::from_str(address).with_context(|| format!("Invalid address: {}", address))
+        })
+        .filter(|address| address.as_ref().map_or(true, |a| seen.insert(*a)))
+        .collect::<Result<Vec<_>>>()?;
+
+    if addresses.is_empty() {
+        bail!("No addresses were provided. Pass them as arguments, or through stdin.");
+    }
+
+    let mut names = reverse_resolve_addresses(&addresses, &network).await?;
+
+    Ok(CommandOutput::Whois(ReverseResolvedNames {
+        network,
+        names: addresses
+            .iter()
+            .map(|address| ReverseResolvedName {
+                package_address: address.to_string(),
+                name: names.remove(address).flatten(),
+            })
+            .collect(),
+    }))
+}
+
+/// Checks the dependencies of the Move.toml file in the current directory for duplicates,
+/// version skew and named addresses pointing to a dependency (`mvr check`).
+pub async fn subcommand_check(network: Option<Network>) -> Result<CommandOutput> {
+    check_sui_version(&MINIMUM_BUILD_SUI_VERSION)?;
+
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+
+    let move_toml = MoveToml::new(
+        env::current_dir()
+            .context("Failed to get current directory")?
+            .join("Move.toml"),
+    )?;
+
+    let dependencies = check_dependencies(&move_toml, &network).await;
+    let conflicts = find_conflicts(&dependencies, &move_toml.get_addresses());
+
+    Ok(CommandOutput::Check(DependencyCheck {
+        network,
+        dependencies,
+        conflicts,
+    }))
+}
+
+/// Diagnoses the environment the CLI (and `--resolve-deps`) runs in (`mvr doctor`).
+pub async fn subcommand_doctor(
+    network: Option<Network>,
+    name: Option<String>,
+) -> Result<CommandOutput> {
+    Ok(CommandOutput::Doctor(run_checks(network, name).await))
+}
+
+/// Lists the resolution results in the local cache (`mvr cache ls`).
+pub fn subcommand_cache_list(network: Option<Network>) -> Result<CommandOutput> {
+    let cache = ResolutionCache::from_env()?;
+
+    Ok(CommandOutput::CacheList(CachedResolutions {
+        ttl_secs: cache.ttl().as_secs(),
+        entries: cache.entries(network.as_ref())?,
+    }))
+}
+
+/// Removes the resolution results from the local cache (`mvr cache clear`). With `all`, the whole
+/// local cache is removed, including the packages kept for offline builds.
+pub fn subcommand_cache_clear(network: Option<Network>, all: bool) -> Result<CommandOutput> {
+    let removed = ResolutionCache::from_env()?.clear(network.as_ref())?;
+
+    if all {
+        PackageCache::from_env()?.clear()?;
+    }
+
+    Ok(CommandOutput::CacheClear(ClearedCache { removed, all }))
+}
+
+/// Verifies that the on-chain modules of a package match the ones built from its registered
+/// git source (`mvr verify`).
+pub async fn subcommand_verify(name: &str, network: Option<Network>) -> Result<CommandOutput> {
+    let network = match network {
+        Some(network) => network,
+        None => get_active_network()?,
+    };
+
+    let (_, package) = query_package(name, &network).await?;
+    let git: SafeGitInfo = package.get_git_info()?.try_into()?;
+
+    let on_chain = query_package_modules(&package.package_address, &network).await?;
+    let original_id = on_chain.original_id;
+    let on_chain_modules = on_chain
+        .modules
+        .into_iter()
+        .map(|(module, bytes)| {
+            let bytes = general_purpose::STANDARD
+                .decode(bytes)
+                .map_err(|e| CliError::UnexpectedParsing(e.to_string()))?;
+            Ok((module, bytes))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    eprintln!(
+        "{} {} @ {} ({})",
+        "[mvr] Building".blue(),
+        git.repository_url,
+        git.tag,
+        git.path
+    );
+
+    let checkout = tempfile::tempdir()?;
+    let package_path = checkout_git_source(&git, checkout.path())?;
+    let source_modules = build_package_modules(&package_path)?;
+
+    let modules = compare_modules(&source_modules, &on_chain_modules, &original_id)?;
+
+    Ok(CommandOutput::Verify(PackageVerification {
+        network,
+        name: package.name,
+        version: package.version,
+        package_address: package.package_address,
+        git,
+        verified: !modules.is_empty() && modules.iter().all(|m| m.status == ModuleStatus::Match),
+        modules,
+    }))
+}
+
 pub async fn subcommand_search_names(
     query: Option<String>,
     limit: Option<u32>,
     cursor: Option<String>,
+    network: Option<Network>,
+    linked_only: bool,
+    org: Option<String>,
 ) -> Result<CommandOutput> {
-    let search_results = search_names(query, limit, cursor).await?;
+    // Accept both formats of an organization (`@mvr`, `mvr.sui`), and a trailing `/`.
+    let org = org
+        .map(|org| {
+            Domain::from_str(org.trim_end_matches('/'))
+                .map_err(|_| anyhow!("Invalid organization: {}", org))
+        })
+        .transpose()?;
+
+    let search_results = search_names(
+        query,
+        limit,
+        cursor,
+        linked_only,
+        org.as_ref(),
+        &network.unwrap_or(Network::Mainnet),
+    )
+    .await?;
 
     Ok(CommandOutput::Search(search_results))
 }
@@ -95,5 +888,16 @@ fn get_chain_id(network: &Network) -> Result<String> {
     match network {
         Network::Testnet => Ok(TESTNET_CHAIN_ID.to_string()),
         Network::Mainnet => Ok(MAINNET_CHAIN_ID.to_string()),
+        Network::Custom(name) => network
+            .config()?
+            .and_then(|config| config.chain_id)
+            .ok_or_else(|| {
+                CliError::MissingNetworkConfig(
+                    name.clone(),
+                    "chain_id".to_string(),
+                    "CHAIN_ID".to_string(),
+                )
+                .into()
+            }),
     }
 }
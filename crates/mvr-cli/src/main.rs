@@ -1,9 +1,13 @@
 use mvr::types::resolver_alt::new_package_resolver;
 
 use std::env;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 
 use anyhow::Result;
 use clap::Parser;
+use mvr::errors::ErrorEnvelope;
+use mvr::utils::package_cache::set_offline_mode;
 use mvr::utils::sui_binary::check_sui_version;
 use mvr::{commands::Command, constants::MINIMUM_BUILD_SUI_VERSION};
 
@@ -21,26 +25,64 @@ struct Cli {
     /// Output the result in JSON format
     #[arg(long, global = true)]
     json: bool,
+
+    /// Only resolve packages from the local cache. Can also be enabled with `MVR_OFFLINE=1`.
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Re-resolve dependencies and refresh `Mvr.lock` instead of using the locked versions.
+    #[arg(long, requires = "resolve_deps")]
+    update: bool,
+
+    /// Resolve the dependencies of every Move package beneath this root in a single pass.
+    #[arg(long, value_name = "ROOT", requires = "resolve_deps")]
+    workspace: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if cli.offline {
+        set_offline_mode(true);
+    }
+
     // If we are in the new package resolver, we wanna special handle it and return early.
     if cli.resolve_deps {
-        check_sui_version(MINIMUM_BUILD_SUI_VERSION)?;
-        new_package_resolver().await?;
+        check_sui_version(&MINIMUM_BUILD_SUI_VERSION)?;
+        new_package_resolver(cli.update, cli.workspace).await?;
         return Ok(());
     }
 
+    // Colors only make sense for humans reading a terminal.
+    if cli.json || !std::io::stdout().is_terminal() {
+        yansi::disable();
+    }
+
     if let Some(command) = cli.command {
-        let output = command.execute().await?;
+        let output = match command.execute().await {
+            Ok(output) => output,
+            Err(e) if cli.json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&ErrorEnvelope::from(&e))?
+                );
+                std::process::exit(1);
+            }
+            Err(e) => return Err(e),
+        };
+
         if cli.json {
             println!("{}", serde_json::to_string_pretty(&output)?);
         } else {
             println!("{}", output);
         }
+
+        // Failures (e.g. a failed verification) are reported like any other output,
+        // but exit with an error.
+        if output.is_failure() {
+            std::process::exit(1);
+        }
     } else {
         let cli = Cli::parse_from(["mvr", "--help"]);
         if let Some(x) = cli.command {
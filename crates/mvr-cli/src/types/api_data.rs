@@ -1,114 +1,319 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
+};
 
-use ::futures::future::try_join_all;
+use ::futures::future::join_all;
 use anyhow::{bail, Result};
-use mvr_types::name::VersionedName;
-use reqwest::Client;
+use mvr_client::{MvrClient, MvrClientError, MAINNET_URL, TESTNET_URL};
+use mvr_types::{
+    api::{
+        DependentsQueryParams, NameAnalyticsResponse, NameSearchQueryParams, PackageDependencies,
+        PackageDependent, PackageModules, PaginatedResponse, TypeResolutionResponse,
+        MAX_BATCH_SIZE,
+    },
+    name::VersionedName,
+    name_service::{Domain, DomainFormat},
+};
 use sui_sdk_types::Address;
+use yansi::Paint;
 
 use crate::{
     errors::CliError,
     types::{
-        api_types::{PackageRequest, ResolutionResponse, SearchNamesResponse},
+        api_types::{PackageRequest, SearchNamesResponse},
         MoveRegistryDependencies, Network,
     },
+    utils::{
+        http,
+        package_cache::{is_offline_mode, PackageCache},
+        resolution_cache::ResolutionCache,
+    },
 };
 
-const MVR_API_MAINNET_URL: &str = "https://mainnet.mvr.mystenlabs.com";
-const MVR_API_TESTNET_URL: &str = "https://testnet.mvr.mystenlabs.com";
-
 const DEFAULT_LIMIT: u32 = 10;
 
 /// Query the MVR API to get Package Information by name.
+///
+/// Names resolved recently (or pinned names resolved before) are answered from the resolution
+/// cache. Successful responses are stored in the local package cache. In offline mode,
+/// the package is only looked up in that cache.
 pub async fn query_package(name: &str, network: &Network) -> Result<(String, PackageRequest)> {
+    query_package_with_refresh(name, network, false).await
+}
+
+/// Like [query_package], but with `refresh` set, unversioned names are always queried from the
+/// MVR API (so they resolve to the actual latest version).
+async fn query_package_with_refresh(
+    name: &str,
+    network: &Network,
+    refresh: bool,
+) -> Result<(String, PackageRequest)> {
     let versioned_name = VersionedName::from_str(name)?;
-    let response = reqwest::get(format!(
-        "{}/v1/names/{}",
-        get_api_url(network)?,
-        versioned_name
-    ))
-    .await
-    .map_err(|e| CliError::Querying(e.to_string()))?;
-
-    if response.status() == reqwest::StatusCode::NOT_FOUND {
-        bail!(CliError::NameNotExists(
-            name.to_string(),
-            network.to_string()
-        ));
+
+    if is_offline_mode() {
+        let Some(package) =
+            PackageCache::from_env()?.get_package_request(network, &versioned_name.to_string())?
+        else {
+            bail!(CliError::NotCachedOffline(
+                name.to_string(),
+                network.to_string()
+            ));
+        };
+
+        return Ok((name.to_string(), package));
+    }
+
+    if let Some(package) = cached_package(network, &versioned_name, refresh) {
+        return Ok((name.to_string(), package));
     }
 
-    let body = response
-        .json()
+    let body: PackageRequest = api_client(network)?
+        .package_by_name(&versioned_name.to_string())
         .await
-        .map_err(|e| CliError::UnexpectedParsing(e.to_string()))?;
+        .map_err(|e| name_error(e, name, network))?
+        .into();
+
+    store_in_cache(network, &[(versioned_name, body.clone())]);
 
     Ok((name.to_string(), body))
 }
 
 pub async fn resolve_name(name: &VersionedName, network: &Network) -> Result<Address> {
-    let response = reqwest::get(format!(
-        "{}/v1/resolution/{}",
-        get_api_url(network)?,
-        name.to_string()
-    ))
-    .await
-    .map_err(|e| CliError::Querying(e.to_string()))?;
-
-    if response.status() == reqwest::StatusCode::NOT_FOUND {
+    let body = api_client(network)?
+        .resolve(&name.to_string())
+        .await
+        .map_err(|e| name_error(e, &name.to_string(), network))?;
+
+    let Some(package_id) = body.package_id else {
         bail!(CliError::NameNotExists(
             name.to_string(),
             network.to_string()
         ));
-    }
+    };
 
-    let body: ResolutionResponse = response
-        .json()
-        .await
-        .map_err(|e| CliError::UnexpectedParsing(e.to_string()))?;
-
-    Ok(Address::from_str(&body.package_id)?)
+    Ok(package_id)
 }
 
 /// Query the MVR API to get Package Information for multiple dependencies.
+///
+/// All names are resolved through the bulk endpoint (falling back to single lookups for
+/// API deployments that do not support it). Names that fail to resolve are reported together.
+///
+/// With `refresh` set, unversioned names are not answered from the resolution cache, for callers
+/// that need the actual latest version of a name (e.g. `mvr update`).
 pub async fn query_multiple_dependencies(
     deps: MoveRegistryDependencies,
     network: &Network,
+    refresh: bool,
 ) -> Result<HashMap<String, PackageRequest>> {
-    let mut requests = vec![];
-    deps.packages.iter().try_for_each(|package| {
-        requests.push(query_package(package, network));
-        Ok::<(), anyhow::Error>(())
-    })?;
-
-    let package_requests =
-        try_join_all(requests)
-            .await?
-            .iter()
-            .fold(HashMap::new(), |mut acc, (name, response)| {
-                acc.insert(name.clone(), response.clone());
-                acc
-            });
-
-    // check if all packages were resolved
-    if package_requests.len() != deps.packages.len() {
-        // find diff
-        let diff = deps.packages.len() - package_requests.len();
-        let diff_packages = deps
-            .packages
-            .iter()
-            .filter(|p| !package_requests.contains_key(*p))
-            .map(|p| p.as_str())
-            .collect::<Vec<_>>();
+    let results = query_each_dependency(&deps.packages, network, refresh).await?;
+
+    let mut package_requests = HashMap::new();
+    let mut errors = vec![];
+
+    for (name, result) in deps.packages.iter().zip(results) {
+        match result {
+            Ok(package) => {
+                package_requests.insert(name.clone(), package);
+            }
+            Err(e) => errors.push(format!("  - {}: {}", name, e)),
+        }
+    }
+
+    if !errors.is_empty() {
         bail!(
-            "Failed to resolve all packages. Missing {} packages: {}",
-            diff,
-            diff_packages.join(", ")
+            "Failed to resolve all packages. Missing {} packages:\n{}",
+            errors.len(),
+            errors.join("\n")
         );
     }
 
     Ok(package_requests)
 }
 
+/// Like [query_multiple_dependencies], but returns a result per name (in the same order),
+/// so names that fail to resolve do not fail the others.
+pub async fn query_each_dependency(
+    names: &[String],
+    network: &Network,
+    refresh: bool,
+) -> Result<Vec<Result<PackageRequest>>> {
+    if names.is_empty() {
+        return Ok(vec![]);
+    }
+
+    if is_offline_mode() {
+        return Ok(query_packages_individually(names, network, refresh).await);
+    }
+
+    match query_packages_in_bulk(names, network, refresh).await {
+        Err(e) if matches!(e.downcast_ref(), Some(CliError::EndpointNotSupported(_))) => {
+            Ok(query_packages_individually(names, network, refresh).await)
+        }
+        results => results,
+    }
+}
+
+/// Resolves names with the `names/bulk` endpoint, returning a result per name (in the same order).
+/// Only valid names missing from the resolution cache are requested, so a malformed name only
+/// fails its own result.
+async fn query_packages_in_bulk(
+    names: &[String],
+    network: &Network,
+    refresh: bool,
+) -> Result<Vec<Result<PackageRequest>>> {
+    let versioned_names = names
+        .iter()
+        .map(|name| VersionedName::from_str(name))
+        .collect::<Vec<_>>();
+
+    let mut packages = HashMap::new();
+    let mut missing = BTreeSet::new();
+
+    for name in versioned_names.iter().flatten() {
+        match cached_package(network, name, refresh) {
+            Some(package) => {
+                packages.insert(name.to_string(), package);
+            }
+            None => {
+                missing.insert(name.to_string());
+            }
+        }
+    }
+
+    let fetched = if missing.is_empty() {
+        vec![]
+    } else {
+        api_client(network)?
+            .bulk_package_by_name(&missing.into_iter().collect::<Vec<_>>())
+            .await
+            .map_err(into_cli_error)?
+            .into_iter()
+            .filter_map(|(name, package)| {
+                Some((VersionedName::from_str(&name).ok()?, package?.into()))
+            })
+            .collect::<Vec<(VersionedName, PackageRequest)>>()
+    };
+
+    store_in_cache(network, &fetched);
+    packages.extend(
+        fetched
+            .into_iter()
+            .map(|(name, package)| (name.to_string(), package)),
+    );
+
+    Ok(names
+        .iter()
+        .zip(versioned_names)
+        .map(|(name, versioned_name)| {
+            let Some(package) = packages.get(&versioned_name?.to_string()) else {
+                bail!(CliError::NameNotExists(
+                    name.to_string(),
+                    network.to_string()
+                ));
+            };
+
+            Ok(package.clone())
+        })
+        .collect())
+}
+
+/// Resolves names one by one (through the local cache in offline mode), returning a result
+/// per name (in the same order).
+async fn query_packages_individually(
+    names: &[String],
+    network: &Network,
+    refresh: bool,
+) -> Vec<Result<PackageRequest>> {
+    let mut results = vec![];
+
+    // Like the bulk endpoint, we only send up to `MAX_BATCH_SIZE` requests at once.
+    for names in names.chunks(MAX_BATCH_SIZE) {
+        let responses = join_all(
+            names
+                .iter()
+                .map(|name| query_package_with_refresh(name, network, refresh)),
+        )
+        .await;
+        results.extend(
+            responses
+                .into_iter()
+                .map(|result| result.map(|(_, package)| package)),
+        );
+    }
+
+    results
+}
+
+/// Looks up a name in the resolution cache. Cache errors are treated as misses.
+///
+/// With `refresh` set, unversioned names are always misses, as their latest version may have
+/// changed since they were cached. Pinned names never change, so they are still looked up.
+fn cached_package(
+    network: &Network,
+    name: &VersionedName,
+    refresh: bool,
+) -> Option<PackageRequest> {
+    if refresh && name.version.is_none() {
+        return None;
+    }
+
+    ResolutionCache::from_env()
+        .and_then(|cache| cache.get_package(network, name))
+        .ok()
+        .flatten()
+}
+
+/// Stores resolved packages in the resolution cache, and in the package cache used by offline mode.
+///
+/// Packages resolved by an unversioned name are also stored under their pinned name
+/// (e.g. `@org/app/3`), as later runs with a lock file look up the locked version.
+fn store_in_cache(network: &Network, packages: &[(VersionedName, PackageRequest)]) {
+    if packages.is_empty() {
+        return;
+    }
+
+    let pinned = packages
+        .iter()
+        .filter(|(name, _)| name.version.is_none())
+        .map(|(name, package)| {
+            let pinned = VersionedName {
+                version: Some(package.version),
+                ..name.clone()
+            };
+            (pinned, package.clone())
+        })
+        .collect::<Vec<_>>();
+    let packages = packages.iter().chain(&pinned).collect::<Vec<_>>();
+
+    let result = PackageCache::from_env()
+        .and_then(|cache| {
+            packages.iter().try_for_each(|(name, package)| {
+                cache.put_package_request(network, &name.to_string(), package)
+            })
+        })
+        .and_then(|_| ResolutionCache::from_env())
+        .and_then(|cache| {
+            cache.put_packages(
+                network,
+                packages.iter().map(|(name, package)| (name, package)),
+            )
+        });
+
+    if let Err(e) = result {
+        warn_cache_error(e);
+    }
+}
+
+fn warn_cache_error(error: anyhow::Error) {
+    eprintln!(
+        "{} {}",
+        "[mvr] Failed to update the local cache:".yellow(),
+        error
+    );
+}
+
 /// Given a search query (and limit, cursor optionally),
 /// returns a paginated list of names that match the query.
 ///
@@ -117,42 +322,262 @@ pub async fn query_multiple_dependencies(
 /// * `search` - The search query.
 /// * `limit` - The limit of results to return. Maximum is 50, default is 10.
 /// * `cursor` - The cursor to paginate through the results.
+/// * `linked_only` - Only return names linked to a package on at least one network.
+/// * `org` - Only return names of this organization. Names of other organizations are also
+///   filtered out here, as older API deployments ignore it (so a page may hold fewer results).
+/// * `network` - The network whose MVR API is queried.
 pub async fn search_names(
     search: Option<String>,
     limit: Option<u32>,
     cursor: Option<String>,
+    linked_only: bool,
+    org: Option<&Domain>,
+    network: &Network,
 ) -> Result<SearchNamesResponse> {
-    let mut params = HashMap::new();
-    params.insert("limit", limit.unwrap_or(DEFAULT_LIMIT).to_string());
+    let params = NameSearchQueryParams {
+        search,
+        cursor,
+        limit: Some(limit.unwrap_or(DEFAULT_LIMIT)),
+        is_linked: linked_only.then_some(true),
+        org: org.map(|org| org.format(DomainFormat::At)),
+    };
 
-    if let Some(search) = search {
-        params.insert("search", search);
+    let mut response = api_client(network)?
+        .search_names(&params)
+        .await
+        .map_err(into_cli_error)?;
+
+    if let Some(org) = org {
+        response.data.retain(|result| {
+            VersionedName::from_str(&result.name).is_ok_and(|name| &name.name.org == org)
+        });
     }
 
-    if let Some(cursor) = cursor {
-        params.insert("cursor", cursor);
+    Ok(response)
+}
+
+/// Query the MVR API for every published version of a name, from `1` up to `latest_version`,
+/// in batches of (at most) `MAX_BATCH_SIZE` names.
+///
+/// Returns the versions that were found, and the ones that could not be queried (e.g. missing
+/// metadata).
+pub async fn query_version_history(
+    name: &VersionedName,
+    latest_version: u64,
+    network: &Network,
+) -> Result<(Vec<PackageRequest>, Vec<u64>)> {
+    let versioned_names = (1..=latest_version)
+        .map(|version| {
+            VersionedName {
+                version: Some(version),
+                ..name.clone()
+            }
+            .to_string()
+        })
+        .collect::<Vec<_>>();
+
+    let mut versions = vec![];
+    let mut missing_versions = vec![];
+
+    for (version, result) in
+        (1..=latest_version).zip(query_each_dependency(&versioned_names, network, false).await?)
+    {
+        match result {
+            Ok(package) => versions.push(package),
+            Err(_) => missing_versions.push(version),
+        }
     }
 
-    let client = Client::new();
+    Ok((versions, missing_versions))
+}
 
-    let response = client
-        .get(format!("{}/v1/names", get_api_url(&Network::Mainnet)?,))
-        .query(&params)
-        .send()
+/// Query the MVR API for the (direct) dependencies of a package address.
+pub async fn query_dependencies(
+    package_address: &str,
+    network: &Network,
+) -> Result<PackageDependencies> {
+    Ok(api_client(network)?
+        .package_dependencies(&parse_address(package_address)?)
         .await
-        .map_err(|e| CliError::Querying(e.to_string()))?;
+        .map_err(into_cli_error)?)
+}
 
-    let body = response
-        .json::<SearchNamesResponse>()
+/// Query the MVR API for the on-chain bytecode of all modules of a package address.
+pub async fn query_package_modules(
+    package_address: &str,
+    network: &Network,
+) -> Result<PackageModules> {
+    Ok(api_client(network)?
+        .package_modules(&parse_address(package_address)?)
         .await
-        .map_err(|e| CliError::UnexpectedParsing(e.to_string()))?;
+        .map_err(into_cli_error)?)
+}
+
+/// Query the MVR API for the top dependents of a package address, ordered by their total calls.
+pub async fn query_dependents(
+    package_address: &str,
+    limit: u32,
+    network: &Network,
+) -> Result<PaginatedResponse<PackageDependent>> {
+    let params = DependentsQueryParams {
+        cursor: None,
+        limit: Some(limit),
+    };
+
+    Ok(api_client(network)?
+        .package_dependents(&parse_address(package_address)?, &params)
+        .await
+        .map_err(into_cli_error)?)
+}
+
+/// Query the MVR API for the (aggregated) analytics of a name.
+pub async fn query_name_analytics(
+    name: &VersionedName,
+    network: &Network,
+) -> Result<NameAnalyticsResponse> {
+    Ok(api_client(network)?
+        .name_analytics(&name.to_string())
+        .await
+        .map_err(into_cli_error)?)
+}
+
+/// Resolves types with MVR names (e.g. `@org/app::module::Type`) to their canonical type tags,
+/// using the `type-resolution` bulk endpoint.
+pub async fn resolve_type_tags(
+    types: &[String],
+    network: &Network,
+) -> Result<HashMap<String, Option<String>>> {
+    let resolution = api_client(network)?
+        .bulk_resolve_types(types)
+        .await
+        .map_err(into_cli_error)?;
 
-    Ok(body)
+    Ok(into_type_tags(resolution))
 }
 
-fn get_api_url(network: &Network) -> Result<&str> {
+/// Resolves types with MVR names to their struct tags, using the package's defining IDs
+/// (the `struct-definition` bulk endpoint).
+pub async fn resolve_struct_definitions(
+    types: &[String],
+    network: &Network,
+) -> Result<HashMap<String, Option<String>>> {
+    let resolution = api_client(network)?
+        .bulk_struct_definitions(types)
+        .await
+        .map_err(into_cli_error)?;
+
+    Ok(into_type_tags(resolution))
+}
+
+fn into_type_tags(
+    resolution: HashMap<String, TypeResolutionResponse>,
+) -> HashMap<String, Option<String>> {
+    resolution
+        .into_iter()
+        .map(|(type_name, response)| (type_name, response.type_tag))
+        .collect()
+}
+
+/// Resolves package addresses to their MVR names, using the `reverse-resolution` bulk endpoint
+/// for addresses missing from the resolution cache. Addresses without a name map to `None`.
+pub async fn reverse_resolve_addresses(
+    addresses: &[Address],
+    network: &Network,
+) -> Result<HashMap<Address, Option<String>>> {
+    let cache = ResolutionCache::from_env().ok();
+
+    let mut names = HashMap::new();
+    let mut missing = vec![];
+
+    for address in addresses {
+        let cached = cache
+            .as_ref()
+            .and_then(|cache| cache.get_name(network, address).ok().flatten());
+
+        match cached {
+            Some(name) => {
+                names.insert(*address, name);
+            }
+            None => missing.push(*address),
+        }
+    }
+
+    if missing.is_empty() {
+        return Ok(names);
+    }
+
+    let mut responses = api_client(network)?
+        .bulk_reverse_resolve(&missing)
+        .await
+        .map_err(into_cli_error)?;
+
+    let fetched = missing
+        .into_iter()
+        .map(|address| {
+            let name = responses
+                .remove(&address)
+                .and_then(|response| response.name);
+            (address, name)
+        })
+        .collect::<HashMap<_, _>>();
+
+    if let Some(cache) = &cache {
+        if let Err(e) = cache.put_names(network, &fetched) {
+            warn_cache_error(e);
+        }
+    }
+
+    names.extend(fetched);
+
+    Ok(names)
+}
+
+/// Returns the MVR API client for a network. Requests go through the shared HTTP client, so
+/// they use the same timeouts, retries and `Mvr-Source` header as the rest of the CLI.
+pub fn api_client(network: &Network) -> Result<MvrClient> {
+    Ok(MvrClient::new(get_api_url(network)?)
+        .with_http_client(http::client().clone())
+        .with_max_retries(http::max_retries()))
+}
+
+/// Maps the errors of the MVR API client to the CLI's errors.
+fn into_cli_error(error: MvrClientError) -> CliError {
+    match error {
+        MvrClientError::Request(e) => CliError::Querying(e.to_string()),
+        MvrClientError::EndpointNotSupported(url) => CliError::EndpointNotSupported(url),
+        MvrClientError::Decode { message, .. } => CliError::UnexpectedParsing(message),
+        e => CliError::Querying(e.to_string()),
+    }
+}
+
+/// Like [into_cli_error], for lookups of `name` (where `404` means the name does not exist).
+fn name_error(error: MvrClientError, name: &str, network: &Network) -> CliError {
+    match error {
+        MvrClientError::NotFound(_) => {
+            CliError::NameNotExists(name.to_string(), network.to_string())
+        }
+        e => into_cli_error(e),
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address, CliError> {
+    Address::from_str(address).map_err(|e| CliError::UnexpectedParsing(e.to_string()))
+}
+
+/// Returns the MVR API URL for a network. The configured `api_url` (if any) takes precedence
+/// over the default URLs, so `mainnet` and `testnet` can also point to a custom deployment.
+pub fn get_api_url(network: &Network) -> Result<String> {
+    if let Some(api_url) = network.config()?.and_then(|config| config.api_url) {
+        return Ok(api_url.trim_end_matches('/').to_string());
+    }
+
     match network {
-        Network::Mainnet => Ok(MVR_API_MAINNET_URL),
-        Network::Testnet => Ok(MVR_API_TESTNET_URL),
+        Network::Mainnet => Ok(MAINNET_URL.to_string()),
+        Network::Testnet => Ok(TESTNET_URL.to_string()),
+        Network::Custom(name) => bail!(CliError::MissingNetworkConfig(
+            name.clone(),
+            "api_url".to_string(),
+            "API_URL".to_string(),
+        )),
     }
 }
@@ -2,8 +2,11 @@ use std::fmt;
 use yansi::Paint;
 
 use anyhow::{bail, Result};
+use mvr_types::api::{NameSearchResponse, PackageByNameResponse, PaginatedResponse};
 use serde::{Deserialize, Serialize};
 
+pub use mvr_types::api::{AnalyticsValue, PackageDependent};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PackageRequest {
     pub name: String,
@@ -29,32 +32,15 @@ pub struct GitInfo {
     pub tag: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct SafeGitInfo {
     pub repository_url: String,
     pub path: String,
     pub tag: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ResolutionResponse {
-    pub package_id: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SearchNamesResponse {
-    pub data: Vec<SinglePackageSearchResult>,
-    pub next_cursor: Option<String>,
-    pub limit: u32,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SinglePackageSearchResult {
-    pub name: String,
-    pub metadata: serde_json::Value,
-    pub mainnet_package_info_id: Option<String>,
-    pub testnet_package_info_id: Option<String>,
-}
+/// A page of `mvr search` results.
+pub type SearchNamesResponse = PaginatedResponse<NameSearchResponse>;
 
 impl fmt::Display for PackageRequest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -63,6 +49,30 @@ impl fmt::Display for PackageRequest {
     }
 }
 
+impl From<PackageByNameResponse> for PackageRequest {
+    fn from(response: PackageByNameResponse) -> Self {
+        let data = response.package_by_name_data;
+
+        Self {
+            name: data.name,
+            metadata: data.metadata,
+            package_info: data.package_info.map(|info| PackageInfo {
+                id: info.id,
+                git_table_id: info.git_table_id,
+                default_name: info.default_name,
+                metadata: info.metadata,
+            }),
+            git_info: data.git_info.map(|git| GitInfo {
+                repository_url: Some(git.repository_url),
+                path: Some(git.path),
+                tag: Some(git.tag),
+            }),
+            version: response.version as u64,
+            package_address: response.package_address.to_string(),
+        }
+    }
+}
+
 impl PackageRequest {
     pub fn get_git_info(&self) -> Result<GitInfo> {
         let Some(git_info) = &self.git_info else {
@@ -2,13 +2,21 @@ pub mod api_data;
 pub mod api_types;
 pub mod resolver_alt;
 
+use std::env;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::OnceLock;
 
 use serde::Deserialize;
 use serde::Serialize;
+use sui_sdk_types::Address;
 
 use crate::errors::CliError;
+use crate::types::api_types::{
+    AnalyticsValue, GitInfo, PackageDependent, PackageRequest, SafeGitInfo,
+};
+use crate::utils::config::{MvrConfig, NetworkConfig};
+use crate::utils::sui_binary::SuiBinaryReport;
 use crate::MAINNET_CHAIN_ID;
 use crate::TESTNET_CHAIN_ID;
 
@@ -17,10 +25,325 @@ pub struct MoveRegistryDependencies {
     pub packages: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+/// A single MVR dependency, as declared in the `[dependencies]` table of a `Move.toml` file.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct MvrDependency {
+    /// The key of the dependency in the `[dependencies]` table.
+    pub alias: String,
+    /// The MVR name of the dependency (the `r.mvr` value, or `r.mvr.name` with a network).
+    pub name: String,
+    /// The network the dependency is resolved on, if it overrides the build environment's
+    /// (the `r.mvr.network` value).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+}
+
+/// The options of `mvr add`.
+#[derive(Debug, Default, Clone)]
+pub struct AddOptions {
+    /// The key of the dependency in the `[dependencies]` table. Defaults to the package name.
+    pub alias: Option<String>,
+    /// Pins the dependency to a version of the name.
+    pub version: Option<u64>,
+    /// Resolves the dependency on this network (instead of the active one), and records it
+    /// as the network of the dependency.
+    pub network: Option<Network>,
+    /// Overwrites a different dependency with the same key.
+    pub force: bool,
+    /// How the Move.toml files are written (`--dry-run` and `--backup`).
+    pub write: WriteOptions,
+}
+
+/// How the commands that change Move.toml files write their changes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WriteOptions {
+    /// Only compute the changes (as a diff), without writing them.
+    pub dry_run: bool,
+    /// Keep the previous content of every changed file as `<file>.bak`.
+    pub backup: bool,
+}
+
+/// The output of the `remove` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RemovedDependency {
+    #[serde(flatten)]
+    pub dependency: MvrDependency,
+    pub dry_run: bool,
+    /// The unified diff of the Move.toml changes (only populated in dry-run mode).
+    pub diff: Option<String>,
+}
+
+/// An MVR dependency of a package, along with its resolved state (when it could be resolved).
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ListedDependency {
+    #[serde(flatten)]
+    pub dependency: MvrDependency,
+    /// The version the dependency is pinned to, if any (e.g. `3` for `@org/app/3`).
+    pub pinned_version: Option<u64>,
+    /// The resolved version of the package. `None` if the dependency was not resolved.
+    pub resolved_version: Option<u64>,
+    /// The resolved package address. `None` if the dependency was not resolved.
+    pub package_address: Option<String>,
+    /// The git information of the resolved version.
+    pub git_info: Option<GitInfo>,
+}
+
+/// The output of the `list` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ListedDependencies {
+    /// The network the dependencies were resolved against. `None` if resolution was skipped.
+    pub network: Option<Network>,
+    pub dependencies: Vec<ListedDependency>,
+}
+
+/// A version-pinned MVR dependency, compared against the latest version of its name.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PinnedDependency {
+    #[serde(flatten)]
+    pub dependency: MvrDependency,
+    /// The version the dependency is currently pinned to.
+    pub pinned_version: u64,
+    /// The latest version of the name on the network.
+    pub latest_version: u64,
+}
+
+impl PinnedDependency {
+    pub fn is_outdated(&self) -> bool {
+        self.latest_version > self.pinned_version
+    }
+}
+
+/// The output of the `outdated` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OutdatedDependencies {
+    pub network: Network,
+    pub dependencies: Vec<PinnedDependency>,
+}
+
+/// A single version pin that was (or, in dry-run mode, would be) rewritten by the `update` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpdatedDependency {
+    pub alias: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// The output of the `update` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpdatedDependencies {
+    pub network: Network,
+    pub updated: Vec<UpdatedDependency>,
+    pub dry_run: bool,
+    /// The unified diff of the Move.toml changes (only populated in dry-run mode).
+    pub diff: Option<String>,
+}
+
+/// The output of the `info` command: everything the MVR API knows about a name.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NameInfo {
+    pub network: Network,
+    /// The requested version of the name (the latest one, unless a version was specified).
+    pub package: PackageRequest,
+    /// Every published version of the name, in ascending order.
+    pub versions: Vec<PackageRequest>,
+    /// The versions that could not be queried from the MVR API.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_versions: Vec<u64>,
+    /// The direct dependencies (package addresses) of the requested version.
+    pub dependencies: Vec<String>,
+    /// The top dependents of the requested version, ordered by their total calls.
+    pub dependents: Vec<PackageDependent>,
+    pub total_dependents: i64,
+    pub analytics: Vec<AnalyticsValue>,
+}
+
+/// The output of the `type` and `struct` commands, in the order the types were requested.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ResolvedTypes {
+    pub network: Network,
+    pub types: Vec<ResolvedType>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ResolvedType {
+    /// The type, as requested (e.g. `@org/app::module::Type`).
+    pub type_name: String,
+    /// The resolved type tag, if the type could be resolved.
+    pub type_tag: Option<String>,
+}
+
+/// The output of the `whois` command, in the order the addresses were requested.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ReverseResolvedNames {
+    pub network: Network,
+    pub names: Vec<ReverseResolvedName>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ReverseResolvedName {
+    pub package_address: String,
+    /// The MVR name of the package, if it has one.
+    pub name: Option<String>,
+}
+
+/// The output of the `cache ls` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CachedResolutions {
+    /// How long the resolutions of unversioned names (and package addresses) are cached for.
+    pub ttl_secs: u64,
+    pub entries: Vec<CachedResolution>,
+}
+
+/// A resolution (or reverse resolution) result in the local cache.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct CachedResolution {
+    pub network: String,
+    pub kind: CachedResolutionKind,
+    /// The MVR name (e.g. `@org/app/3`), or the package address for reverse resolutions.
+    pub key: String,
+    /// The package address the name resolves to, or the MVR name of the package address.
+    pub value: Option<String>,
+    /// When the result was cached, in seconds since the Unix epoch.
+    pub cached_at: u64,
+    /// When the result expires, in seconds since the Unix epoch. Pinned names never expire.
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CachedResolutionKind {
+    Resolution,
+    ReverseResolution,
+}
+
+/// The output of the `cache clear` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClearedCache {
+    /// The number of resolution results removed.
+    pub removed: usize,
+    /// Whether the whole local cache (including the packages kept for offline builds) was removed.
+    pub all: bool,
+}
+
+/// The output of the `verify` command: how the modules built from the git source of a package
+/// compare to its on-chain modules.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PackageVerification {
+    pub network: Network,
+    pub name: String,
+    pub version: u64,
+    pub package_address: String,
+    pub git: SafeGitInfo,
+    /// Whether every module of the package matches its on-chain bytecode.
+    pub verified: bool,
+    pub modules: Vec<ModuleVerification>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ModuleVerification {
+    pub module: String,
+    pub status: ModuleStatus,
+    /// The sha256 digest of the module built from source, if it exists.
+    pub source_digest: Option<String>,
+    /// The sha256 digest of the on-chain module, if it exists.
+    pub on_chain_digest: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleStatus {
+    Match,
+    Mismatch,
+    /// The module exists on-chain, but not in the source.
+    MissingFromSource,
+    /// The module exists in the source, but not on-chain.
+    MissingOnChain,
+}
+
+/// The output of the `doctor` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DoctorReport {
+    /// The detected `sui` binary, if it could be run.
+    pub sui_binary: Option<SuiBinaryReport>,
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    pub fn has_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.status == CheckStatus::Fail)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// A suggested fix, for checks that did not pass.
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// The output of the `check` command.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DependencyCheck {
+    pub network: Network,
+    pub dependencies: Vec<CheckedDependency>,
+    pub conflicts: Vec<DependencyConflict>,
+}
+
+/// A dependency of a package, along with the on-chain package it points to (when it is known).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct CheckedDependency {
+    /// The key of the dependency in the `[dependencies]` table.
+    pub alias: String,
+    /// The MVR name, git source or local path of the dependency.
+    pub source: String,
+    pub original_id: Option<Address>,
+    pub published_at: Option<Address>,
+    /// Why the addresses of the dependency are not known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct DependencyConflict {
+    pub kind: ConflictKind,
+    /// The original ID of the package the conflicting dependencies point to.
+    pub original_id: Address,
+    /// The aliases of the conflicting dependencies (or the named address, for `named_address` conflicts).
+    pub dependencies: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Several dependencies point to the same version of a package.
+    Duplicate,
+    /// Several dependencies point to different versions of a package.
+    VersionSkew,
+    /// A named address of the `[addresses]` table is set to the address of a dependency.
+    NamedAddress,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
 pub enum Network {
     Mainnet,
     Testnet,
+    /// A network configured in the MVR config (or through environment variables).
+    #[serde(untagged)]
+    Custom(String),
 }
 
 impl Network {
@@ -28,7 +351,37 @@ impl Network {
         match chain_identifier {
             MAINNET_CHAIN_ID => Ok(Network::Mainnet),
             TESTNET_CHAIN_ID => Ok(Network::Testnet),
-            _ => Err(CliError::NetworkNotSupported),
+            _ => load_config()?
+                .network_by_chain_id(chain_identifier)
+                .map(Network::from_name)
+                .ok_or(CliError::NetworkNotSupported),
+        }
+    }
+
+    /// Maps a network name to a `Network`, without checking that custom networks are configured.
+    fn from_name(name: &str) -> Self {
+        match name {
+            "mainnet" => Network::Mainnet,
+            "testnet" => Network::Testnet,
+            _ => Network::Custom(name.to_string()),
+        }
+    }
+
+    /// Returns the configuration of the network (if any), from the MVR config.
+    ///
+    /// A config file that cannot be loaded only fails custom networks: `mainnet` and `testnet`
+    /// still work with their defaults (and the environment variable overrides).
+    pub fn config(&self) -> Result<Option<NetworkConfig>, CliError> {
+        let name = self.to_string();
+
+        match (self, load_config()) {
+            (_, Ok(config)) => Ok(config.network(&name).cloned()),
+            (Network::Custom(_), Err(e)) => Err(e),
+            (_, Err(_)) => {
+                let mut config = MvrConfig::default();
+                config.apply_env_overrides(env::vars());
+                Ok(config.network(&name).cloned())
+            }
         }
     }
 }
@@ -38,6 +391,7 @@ impl fmt::Display for Network {
         match self {
             Network::Mainnet => write!(f, "mainnet"),
             Network::Testnet => write!(f, "testnet"),
+            Network::Custom(name) => write!(f, "{}", name),
         }
     }
 }
@@ -49,7 +403,19 @@ impl FromStr for Network {
         match s {
             "mainnet" => Ok(Network::Mainnet),
             "testnet" => Ok(Network::Testnet),
+            _ if load_config()?.network(s).is_some() => Ok(Network::Custom(s.to_string())),
             _ => Err(CliError::InvalidNetwork(s.to_string())),
         }
     }
 }
+
+/// The MVR config, which is loaded once per process (when a custom network or an override
+/// is first needed).
+fn load_config() -> Result<&'static MvrConfig, CliError> {
+    static CONFIG: OnceLock<Result<MvrConfig, String>> = OnceLock::new();
+
+    CONFIG
+        .get_or_init(|| MvrConfig::load().map_err(|e| format!("{:#}", e)))
+        .as_ref()
+        .map_err(|e| CliError::InvalidConfig(e.clone()))
+}
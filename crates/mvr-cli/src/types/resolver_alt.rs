@@ -1,20 +1,30 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     env,
-    io::stdin,
+    io::{stdin, BufRead},
+    path::PathBuf,
     str::FromStr,
 };
 
 use anyhow::Result;
-use jsonrpc::types::{BatchRequest, JsonRpcResult, RemoteError, RequestID, Response, TwoPointZero};
+use jsonrpc::types::{JsonRpcResult, RemoteError, Request, RequestID, Response, TwoPointZero};
 use mvr_types::name::VersionedName;
-use serde::Deserialize;
+use serde::{de::IgnoredAny, Deserialize};
 use sui_sdk_types::Address;
 use yansi::Paint;
 
 use crate::{
-    types::{api_data::query_multiple_dependencies, MoveRegistryDependencies, Network},
-    utils::sui_binary::cache_package,
+    constants::EnvVariables,
+    types::{
+        api_data::query_each_dependency,
+        api_types::{PackageRequest, SafeGitInfo},
+        Network,
+    },
+    utils::{
+        lockfile::{LockedPackage, MvrLock},
+        sui_binary::cache_package,
+        workspace::Workspace,
+    },
 };
 
 #[derive(Deserialize, Debug)]
@@ -23,135 +33,455 @@ struct ResolveRequest {
     // We expect a "chain-id" populated here, or we'll resolve on all known chain ids (mainnet / testnet)
     env: Option<String>,
 
-    // we expect the "data" to be a plain string, being a MVR Name.
-    data: String,
+    // The `r.mvr` value of the dependency: a MVR Name, or a table with its name and network.
+    data: ResolveData,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum ResolveData {
+    /// A plain MVR Name (`r.mvr = "@org/app"`), resolved on the network of the build environment.
+    Name(String),
+    /// A MVR Name resolved on another network (`r.mvr.name = "@org/app"`, `r.mvr.network = "testnet"`).
+    WithNetwork { name: String, network: String },
+}
+
+impl ResolveData {
+    fn name(&self) -> &str {
+        match self {
+            ResolveData::Name(name) | ResolveData::WithNetwork { name, .. } => name,
+        }
+    }
+}
+
+/// Where the network of a request comes from: the build environment (its chain id), or the
+/// network override of the dependency.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum NetworkSource {
+    Env(String),
+    Override(String),
+}
+
+impl NetworkSource {
+    fn of(request: &ResolveRequest) -> Self {
+        match &request.data {
+            ResolveData::Name(_) => NetworkSource::Env(request.env.clone().unwrap_or_default()),
+            ResolveData::WithNetwork { network, .. } => NetworkSource::Override(network.clone()),
+        }
+    }
+
+    fn network(&self) -> Result<Network, String> {
+        match self {
+            NetworkSource::Env(env) => get_normalized_network(env).map_err(|e| e.to_string()),
+            NetworkSource::Override(network) => {
+                Network::from_str(network).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+/// The code of names that could not be resolved (e.g. unknown names, or address mismatches).
+const RESOLUTION_ERROR: i32 = 404;
+
 /// The new (package-alt) resolver which uses `sui`'s `cache-package` to bulk-resolve & cache packages.
-pub async fn new_package_resolver() -> Result<()> {
-    let input = parse_input();
+///
+/// Names already recorded in `Mvr.lock` resolve to their locked version, unless `update_lock` is set
+/// (or `MVR_UPDATE_LOCK=1`), in which case they are re-resolved and the lock file is refreshed.
+///
+/// With a `workspace` root (or `MVR_WORKSPACE`), the MVR names of every package beneath it are
+/// resolved (and cached) in the same bulk pass, and a single `Mvr.lock` is kept at the workspace root.
+pub async fn new_package_resolver(update_lock: bool, workspace: Option<PathBuf>) -> Result<()> {
+    // The request may span multiple lines, so we read until it is a complete JSON value.
+    let mut input = String::new();
+
+    for line in stdin().lock().lines() {
+        input.push_str(&line?);
+        input.push('\n');
+
+        if is_complete_json(&input) {
+            break;
+        }
+    }
+
+    println!("{}", resolve_batch(&input, update_lock, workspace).await?);
+    Ok(())
+}
+
+/// Resolves the JSON-RPC batch requests (as sent by the Sui CLI) in `request`, and returns
+/// the JSON-RPC response of each batch, one per line.
+///
+/// Invalid requests, and names that fail to resolve, get an error response of their own,
+/// without failing the other requests of the batch.
+pub async fn resolve_batch(
+    request: &str,
+    update_lock: bool,
+    workspace: Option<PathBuf>,
+) -> Result<String> {
+    let workspace = workspace
+        .or_else(|| env::var_os(EnvVariables::MvrWorkspace.to_string()).map(PathBuf::from))
+        .map(|root| Workspace::discover(&root))
+        .transpose()?;
 
-    if input.is_empty() {
+    if let Some(workspace) = &workspace {
+        warn_pin_conflicts(workspace);
+    }
+
+    let lock_dir = match &workspace {
+        Some(workspace) => workspace.root.clone(),
+        None => env::current_dir()?,
+    };
+
+    let mut resolver = Resolver {
+        lock_path: MvrLock::path_for(&lock_dir),
+        lock: MvrLock::read_from_file(&MvrLock::path_for(&lock_dir))?,
+        lock_changed: false,
+        update_lock: update_lock || is_update_lock_env_set(),
+        workspace,
+    };
+
+    let mut outputs = vec![];
+
+    for value in serde_json::Deserializer::from_str(request).into_iter::<serde_json::Value>() {
+        let batch = value
+            .map_err(|e| remote_error(PARSE_ERROR, format!("Invalid JSON: {}", e)))
+            .and_then(parse_batch);
+
+        let output = match batch {
+            Ok(requests) => serde_json::to_string(&resolver.resolve(requests).await)?,
+            Err(error) => {
+                let failed = error.code == PARSE_ERROR;
+                outputs.push(serde_json::to_string(&format_result::<serde_json::Value>(
+                    0,
+                    JsonRpcResult::Err { error },
+                ))?);
+
+                // We cannot find where the next value starts after a parsing error.
+                if failed {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        outputs.push(output);
+    }
+
+    if outputs.is_empty() {
         let response: Response<serde_json::Value> = format_result(
             0,
             JsonRpcResult::Err {
-                error: RemoteError {
-                    code: 404,
-                    message: "You requested a batch resolution, but no names were provided"
-                        .to_string(),
-                    data: None,
-                },
+                error: remote_error(
+                    INVALID_REQUEST,
+                    "You requested a batch resolution, but no names were provided".to_string(),
+                ),
             },
         );
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&response).unwrap_or_default()
-        );
-        return Ok(());
+        outputs.push(serde_json::to_string(&response)?);
+    }
+
+    if resolver.lock_changed {
+        resolver.lock.write_to_file(&resolver.lock_path)?;
     }
 
-    // Peek into the first value and extract the network we'll use for the resolution.
-    let network = get_normalized_network(
-        &input
-            .first_key_value()
-            .unwrap()
-            .1
-            .env
-            .clone()
-            .unwrap_or_default(),
-    )?;
+    Ok(outputs.join("\n"))
+}
+
+/// A request of a batch, or the error to respond with if it is not a valid `resolve` request.
+type ParsedRequest = (RequestID, Result<ResolveRequest, RemoteError>);
+
+struct Resolver {
+    workspace: Option<Workspace>,
+    lock_path: PathBuf,
+    lock: MvrLock,
+    lock_changed: bool,
+    update_lock: bool,
+}
+
+impl Resolver {
+    /// Resolves the requests of a batch, grouped by the network of their `env` (or the network
+    /// override of their dependency).
+    async fn resolve(&mut self, requests: Vec<ParsedRequest>) -> Vec<Response<serde_json::Value>> {
+        let mut networks: BTreeMap<NetworkSource, Result<Network, String>> = BTreeMap::new();
+        let mut names: BTreeMap<Network, BTreeSet<String>> = BTreeMap::new();
+
+        for (_, request) in &requests {
+            let Ok(request) = request else {
+                continue;
+            };
+            let source = NetworkSource::of(request);
+
+            let network = networks
+                .entry(source.clone())
+                .or_insert_with(|| source.network());
+
+            if let Ok(network) = network {
+                names
+                    .entry(network.clone())
+                    .or_default()
+                    .insert(request.data.name().to_string());
+            }
+        }
+
+        // network -> requested name -> resolved dependency (or error)
+        let mut resolved = BTreeMap::new();
+
+        for (network, names) in names {
+            let packages = self.resolve_names(&network, names).await;
+            resolved.insert(network, packages);
+        }
+
+        requests
+            .into_iter()
+            .map(|(id, request)| {
+                let result = request.and_then(|request| {
+                    let network = networks[&NetworkSource::of(&request)]
+                        .clone()
+                        .map_err(|message| remote_error(INVALID_PARAMS, message))?;
+                    let name = request.data.name();
 
-    let mut names = BTreeSet::new();
+                    resolved
+                        .get(&network)
+                        .and_then(|dependencies| dependencies.get(name))
+                        .cloned()
+                        .unwrap_or_else(|| Err(format!("No name entries found for {}", name)))
+                        .map_err(|message| remote_error(RESOLUTION_ERROR, message))
+                });
 
-    for (_, request) in &input {
-        let name = VersionedName::from_str(&request.data)?;
-        names.insert(name.to_string());
+                match result {
+                    Ok(result) => format_result(id, JsonRpcResult::Ok { result }),
+                    Err(error) => format_result(id, JsonRpcResult::Err { error }),
+                }
+            })
+            .collect()
     }
 
-    let package_responses = query_multiple_dependencies(
-        MoveRegistryDependencies {
-            packages: names.iter().map(|n| n.to_string()).collect(),
-        },
-        &network,
-    )
-    .await?;
+    /// Resolves (and caches) the requested names on a network, along with the names of the
+    /// workspace, and records them in the lock file. Returns the dependency of each requested name.
+    async fn resolve_names(
+        &mut self,
+        network: &Network,
+        requested: BTreeSet<String>,
+    ) -> BTreeMap<String, Result<serde_json::Value, String>> {
+        let mut all_names = requested.clone();
+
+        if let Some(workspace) = &self.workspace {
+            all_names.extend(workspace.mvr_names());
+        }
 
-    // Cache all packages and do an address/version check!
-    for (name, response) in &package_responses {
+        // requested name -> (normalized name, name we query the API for)
+        let mut query_names = BTreeMap::new();
+        let mut results = BTreeMap::new();
+
+        for data in all_names {
+            let name = match VersionedName::from_str(&data) {
+                Ok(name) => name,
+                Err(e) => {
+                    results.insert(data, Err(e.to_string()));
+                    continue;
+                }
+            };
+            let normalized = name.to_string();
+
+            // If the name is locked, we resolve the locked version instead of the latest one.
+            let query_name = match self.lock.get(network, &normalized) {
+                Some(locked) if !self.update_lock => VersionedName {
+                    version: Some(locked.version),
+                    ..name
+                }
+                .to_string(),
+                _ => normalized.clone(),
+            };
+
+            query_names.insert(data, (normalized, query_name));
+        }
+
+        let unique_names = query_names
+            .values()
+            .map(|(_, query_name)| query_name.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let responses: BTreeMap<String, Result<PackageRequest, String>> =
+            match query_each_dependency(&unique_names, network, self.update_lock).await {
+                Ok(responses) => unique_names
+                    .into_iter()
+                    .zip(responses)
+                    .map(|(name, response)| (name, response.map_err(|e| e.to_string())))
+                    .collect(),
+                Err(e) => unique_names
+                    .into_iter()
+                    .map(|name| (name, Err(e.to_string())))
+                    .collect(),
+            };
+
+        // Cache all packages and do an address/version check!
+        for (data, (name, query_name)) in query_names {
+            let result = responses[&query_name]
+                .clone()
+                .and_then(|response| self.check_and_lock(network, &name, &query_name, response));
+
+            if let Err(e) = &result {
+                if !requested.contains(&data) {
+                    eprintln!(
+                        "{} {} {}",
+                        "[mvr] warning: could not resolve workspace dependency".yellow(),
+                        data.yellow().bold(),
+                        e
+                    );
+                }
+            }
+
+            results.insert(data, result);
+        }
+
+        results.retain(|data, _| requested.contains(data));
+        results
+    }
+
+    /// Caches a resolved package, and checks it against its address and the lock file.
+    /// Returns the dependency the name resolves to.
+    fn check_and_lock(
+        &mut self,
+        network: &Network,
+        name: &str,
+        query_name: &str,
+        response: PackageRequest,
+    ) -> Result<serde_json::Value, String> {
         eprintln!(
             "{}: {:?} {} {}",
             "[mvr] resolving".blue(),
-            name.blue().bold(),
+            query_name.blue().bold(),
             "on network: ".blue(),
             network.blue().bold(),
         );
 
-        let cached_package = cache_package(response.clone(), &network)?;
-        let address = Address::from_str(&response.package_address.clone())?;
+        let git_info = response.get_git_info().map_err(|e| {
+            format!(
+                "Package with name {} does not have git info for env {} ({})",
+                name, network, e
+            )
+        })?;
+        let git = SafeGitInfo::try_from(git_info.clone()).map_err(|e| e.to_string())?;
+
+        let cached_package = cache_package(response.clone(), network).map_err(|e| e.to_string())?;
+        let address = Address::from_str(&response.package_address).map_err(|e| e.to_string())?;
 
         // Detect address missmatches to protect users.
         if address != cached_package.original_id && address != cached_package.published_at {
-            let err_message = format!("Package {} on network {} has an address missmatch. Neither of its addresses match the expected address", name, network);
-            let response: Response<serde_json::Value> = format_result(
-                0,
-                JsonRpcResult::Err {
-                    error: RemoteError {
-                        code: 404,
-                        message: err_message,
-                        data: None,
-                    },
-                },
-            );
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&response).unwrap_or_default()
-            );
-            return Ok(());
+            return Err(format!("Package {} on network {} has an address missmatch. Neither of its addresses match the expected address", name, network));
+        }
+
+        let resolved = LockedPackage {
+            version: response.version,
+            package_address: response.package_address.clone(),
+            original_id: cached_package.original_id,
+            git,
+        };
+
+        // Detect packages that no longer match what was recorded in the lock file.
+        if let Some(locked) = self.lock.get(network, name).filter(|_| !self.update_lock) {
+            if locked != &resolved {
+                return Err(format!("Package {} on network {} does not match the version recorded in {}. Run with `--update` (or `{}=1`) to update the lock file", name, network, self.lock_path.display(), EnvVariables::MvrUpdateLock));
+            }
         }
+
+        self.lock_changed |= self.lock.insert(network, name, resolved);
+        Ok(
+            serde_json::json!({ "git": git_info.repository_url, "rev": git_info.tag, "subdir": git_info.path }),
+        )
     }
+}
 
-    let responses: Vec<Response<serde_json::Value>> = input
-        .into_iter()
-        .map(|(id, request)| {
-            let Some(response) = package_responses.get(&request.data) else {
-                return format_result(id, JsonRpcResult::Err {
-                    error: RemoteError { code: 404, message: format!("No name entries found for {}", request.data), data: None } 
-                });
-            };
+fn warn_pin_conflicts(workspace: &Workspace) {
+    for conflict in workspace.pin_conflicts() {
+        let pins = conflict
+            .pins
+            .iter()
+            .map(|(path, name)| format!("{} ({})", name, path.display()))
+            .collect::<Vec<_>>();
 
-            let Some(git_info) = &response.git_info else {
-                return format_result(id, JsonRpcResult::Err {
-                    error: RemoteError { code: 404, message: format!("Package with name {} does not have git info for env {}", request.data, network), data: None } 
-                });
-            };
+        eprintln!(
+            "{} {} {} {}",
+            "[mvr] warning:".yellow(),
+            conflict.name.yellow().bold(),
+            "is requested with conflicting versions across the workspace:".yellow(),
+            pins.join(", ")
+        );
+    }
+}
 
-            format_result(id, JsonRpcResult::Ok {
-                result: serde_json::json!({ "git": git_info.repository_url, "rev": git_info.tag, "subdir": git_info.path })
-            })
-        })
-        .collect();
+/// Parses a batch (or a single request) into its requests. Requests that are not valid `resolve`
+/// requests are kept, so they can be answered with an error.
+fn parse_batch(value: serde_json::Value) -> Result<Vec<ParsedRequest>, RemoteError> {
+    let requests = match value {
+        serde_json::Value::Array(requests) => requests,
+        request @ serde_json::Value::Object(_) => vec![request],
+        _ => {
+            return Err(remote_error(
+                INVALID_REQUEST,
+                "Expected a JSON-RPC request, or a batch of requests".to_string(),
+            ))
+        }
+    };
 
-    let json = serde_json::to_string(&responses).unwrap_or_default();
+    if requests.is_empty() {
+        return Err(remote_error(
+            INVALID_REQUEST,
+            "You requested a batch resolution, but no names were provided".to_string(),
+        ));
+    }
 
-    println!("{json}");
-    Ok(())
+    Ok(requests.into_iter().map(parse_request).collect())
+}
+
+fn parse_request(value: serde_json::Value) -> ParsedRequest {
+    let id = value.get("id").and_then(|id| id.as_u64()).unwrap_or(0);
+
+    let request = serde_json::from_value::<Request<serde_json::Value>>(value)
+        .map_err(|e| remote_error(INVALID_REQUEST, format!("Invalid request: {}", e)))
+        .and_then(|request| {
+            if request.method != "resolve" {
+                return Err(remote_error(
+                    METHOD_NOT_FOUND,
+                    format!(
+                        "Unknown method `{}`. Only `resolve` is supported",
+                        request.method
+                    ),
+                ));
+            }
+
+            serde_json::from_value::<ResolveRequest>(request.params)
+                .map_err(|e| remote_error(INVALID_PARAMS, format!("Invalid params: {}", e)))
+        });
+
+    (id, request)
 }
 
-/// Read a [Request] from [stdin]
-fn parse_input() -> BTreeMap<RequestID, ResolveRequest> {
-    let mut line = String::new();
-    stdin().read_line(&mut line).expect("stdin can be read");
+/// Whether `input` holds a complete JSON value (or cannot become one by reading more of it).
+fn is_complete_json(input: &str) -> bool {
+    !matches!(
+        serde_json::from_str::<IgnoredAny>(input),
+        Err(e) if e.is_eof()
+    )
+}
 
-    let batch: BatchRequest<ResolveRequest> = serde_json::from_str(&line)
-        .expect("External resolver must be passed a JSON RPC batch request");
+fn remote_error(code: i32, message: String) -> RemoteError {
+    RemoteError {
+        code,
+        message,
+        data: None,
+    }
+}
 
-    batch
-        .into_iter()
-        .map(|req| {
-            assert!(req.method == "resolve");
-            (req.id, req.params)
-        })
-        .collect()
+fn is_update_lock_env_set() -> bool {
+    env::var(EnvVariables::MvrUpdateLock.to_string())
+        .is_ok_and(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
 }
 
 /// Returns the "normalized" network:
@@ -0,0 +1,109 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{constants::EnvVariables, utils::paths::mvr_home_dir};
+
+const CONFIG_FILE: &str = "config.toml";
+const NETWORK_ENV_PREFIX: &str = "MVR_NETWORK_";
+const CHAIN_ID_ENV_SUFFIX: &str = "_CHAIN_ID";
+const API_URL_ENV_SUFFIX: &str = "_API_URL";
+
+/// The CLI configuration, which maps network names to their chain identifier and MVR API URL.
+///
+/// It is read from `~/.mvr/config.toml` (or the file set in `MVR_CONFIG`), e.g.:
+///
+/// ```toml
+/// [networks.devnet]
+/// chain_id = "aba3e445"
+/// api_url = "https://mvr.devnet.example.com"
+/// ```
+///
+/// Every value can also be set (or overridden) with the `MVR_NETWORK_<NAME>_CHAIN_ID` and
+/// `MVR_NETWORK_<NAME>_API_URL` environment variables.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MvrConfig {
+    #[serde(default)]
+    pub networks: BTreeMap<String, NetworkConfig>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkConfig {
+    pub chain_id: Option<String>,
+    pub api_url: Option<String>,
+}
+
+impl MvrConfig {
+    /// Loads the config file (if it exists), and applies the environment variable overrides.
+    pub fn load() -> Result<Self> {
+        let mut config = Self::read_from_file(&config_path()?)?;
+        config.apply_env_overrides(env::vars());
+        Ok(config)
+    }
+
+    /// Reads a config file from disk. A missing file is treated as an empty config.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        toml_edit::de::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    }
+
+    /// Applies `MVR_NETWORK_<NAME>_CHAIN_ID` and `MVR_NETWORK_<NAME>_API_URL` variables
+    /// on top of the config. `<NAME>` is matched against lowercase network names.
+    pub fn apply_env_overrides(&mut self, vars: impl IntoIterator<Item = (String, String)>) {
+        for (key, value) in vars {
+            let Some(rest) = key.strip_prefix(NETWORK_ENV_PREFIX) else {
+                continue;
+            };
+
+            if let Some(name) = rest.strip_suffix(CHAIN_ID_ENV_SUFFIX) {
+                self.network_entry(name).chain_id = Some(value);
+            } else if let Some(name) = rest.strip_suffix(API_URL_ENV_SUFFIX) {
+                self.network_entry(name).api_url = Some(value);
+            }
+        }
+    }
+
+    pub fn network(&self, name: &str) -> Option<&NetworkConfig> {
+        self.networks.get(name)
+    }
+
+    /// Returns the name of the configured network with the given chain identifier.
+    pub fn network_by_chain_id(&self, chain_id: &str) -> Option<&str> {
+        self.networks
+            .iter()
+            .find(|(_, config)| config.chain_id.as_deref() == Some(chain_id))
+            .map(|(name, _)| name.as_str())
+    }
+
+    fn network_entry(&mut self, name: &str) -> &mut NetworkConfig {
+        self.networks.entry(name.to_lowercase()).or_default()
+    }
+}
+
+/// The path of the config file, to show in messages.
+pub fn config_path_display() -> String {
+    config_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or(format!("~/.mvr/{}", CONFIG_FILE))
+}
+
+/// Returns the path of the config file (`MVR_CONFIG`, or `~/.mvr/config.toml`).
+pub fn config_path() -> Result<PathBuf> {
+    if let Ok(path) = env::var(EnvVariables::MvrConfig.to_string()) {
+        return Ok(PathBuf::from(path));
+    }
+
+    Ok(mvr_home_dir()?.join(CONFIG_FILE))
+}
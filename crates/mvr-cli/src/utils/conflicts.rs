@@ -0,0 +1,292 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{bail, Result};
+use sui_sdk_types::Address;
+use toml_edit::{DocumentMut, Item};
+
+use crate::{
+    errors::CliError,
+    types::{
+        api_data::query_each_dependency, api_types::SafeGitInfo, CheckedDependency, ConflictKind,
+        DependencyConflict, Network,
+    },
+    utils::{
+        manifest::{DependencySource, MoveToml, ADDRESSES_KEY, PUBLISHED_AT_KEY},
+        sui_binary::{cache_git_package, cache_package, SuiCachePackageResponse},
+    },
+};
+
+const SUI_REPOSITORY: &str = "github.com/MystenLabs/sui";
+const SUI_FRAMEWORK_DIR: &str = "crates/sui-framework/";
+
+/// Finds the on-chain package every dependency of a `Move.toml` file points to, on `network`:
+/// - MVR dependencies are resolved through the MVR API (and cached with `sui move cache-package`).
+/// - Git dependencies are cached with `sui move cache-package`.
+/// - Local dependencies are read from the publication files of the local package.
+///
+/// The Sui framework packages are skipped, as they are never published through MVR.
+pub async fn check_dependencies(move_toml: &MoveToml, network: &Network) -> Vec<CheckedDependency> {
+    let sources = move_toml
+        .get_dependency_sources()
+        .into_iter()
+        .filter(|(_, source)| !is_framework_dependency(source))
+        .collect::<Vec<_>>();
+
+    let mvr_names = sources
+        .iter()
+        .filter_map(|(_, source)| match source {
+            DependencySource::Mvr(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let mvr_packages: BTreeMap<String, Result<SuiCachePackageResponse, String>> =
+        match query_each_dependency(&mvr_names, network, false).await {
+            Ok(results) => mvr_names
+                .into_iter()
+                .zip(results)
+                .map(|(name, result)| {
+                    let package = result
+                        .and_then(|package| cache_package(package, network))
+                        .map_err(|e| e.to_string());
+                    (name, package)
+                })
+                .collect(),
+            Err(e) => mvr_names
+                .into_iter()
+                .map(|name| (name, Err(e.to_string())))
+                .collect(),
+        };
+
+    let package_dir = move_toml
+        .path
+        .as_ref()
+        .and_then(|path| path.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    sources
+        .into_iter()
+        .map(|(alias, source)| {
+            let (description, addresses) = match &source {
+                DependencySource::Mvr(name) => (
+                    name.clone(),
+                    mvr_packages[name].clone().map(package_addresses),
+                ),
+                DependencySource::Git(git_info) => (
+                    describe_git_dependency(git_info),
+                    cache_git_package(git_info, network)
+                        .map(package_addresses)
+                        .map_err(|e| e.to_string()),
+                ),
+                DependencySource::Local(path) => (
+                    format!("local {}", path.display()),
+                    local_package_addresses(&package_dir.join(path), network)
+                        .map_err(|e| e.to_string()),
+                ),
+                DependencySource::Other => (
+                    "unknown source".to_string(),
+                    Err("Unsupported dependency source".to_string()),
+                ),
+            };
+
+            let (original_id, published_at, error) = match addresses {
+                Ok((original_id, published_at)) => (original_id, published_at, None),
+                Err(e) => (None, None, Some(e)),
+            };
+
+            CheckedDependency {
+                alias,
+                source: description,
+                original_id,
+                published_at,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// Finds the dependencies that point to the same on-chain package, either at the same version
+/// (duplicates) or at different versions (version skew), and the named addresses set to the
+/// address of a dependency.
+pub fn find_conflicts(
+    dependencies: &[CheckedDependency],
+    addresses: &BTreeMap<String, String>,
+) -> Vec<DependencyConflict> {
+    let mut packages: BTreeMap<Address, Vec<&CheckedDependency>> = BTreeMap::new();
+
+    for dependency in dependencies {
+        if let Some(original_id) = dependency.original_id {
+            packages.entry(original_id).or_default().push(dependency);
+        }
+    }
+
+    let mut conflicts = vec![];
+
+    for (original_id, dependencies) in &packages {
+        if dependencies.len() < 2 {
+            continue;
+        }
+
+        let versions = dependencies
+            .iter()
+            .map(|dependency| dependency.published_at)
+            .collect::<BTreeSet<_>>();
+        let aliases = dependencies
+            .iter()
+            .map(|dependency| dependency.alias.clone())
+            .collect::<Vec<_>>();
+
+        let (kind, message) = if versions.len() == 1 {
+            (
+                ConflictKind::Duplicate,
+                format!(
+                    "{} depend on the same package ({})",
+                    aliases.join(", "),
+                    original_id
+                ),
+            )
+        } else {
+            let versions = dependencies
+                .iter()
+                .map(|dependency| {
+                    let published_at = dependency
+                        .published_at
+                        .map(|address| address.to_string())
+                        .unwrap_or("an unknown address".to_string());
+                    format!("{} (published at {})", dependency.alias, published_at)
+                })
+                .collect::<Vec<_>>();
+
+            (
+                ConflictKind::VersionSkew,
+                format!(
+                    "{} depend on different versions of the same package ({})",
+                    versions.join(", "),
+                    original_id
+                ),
+            )
+        };
+
+        conflicts.push(DependencyConflict {
+            kind,
+            original_id: *original_id,
+            dependencies: aliases,
+            message,
+        });
+    }
+
+    for (name, address) in addresses {
+        let Ok(address) = Address::from_str(address) else {
+            continue;
+        };
+
+        for dependency in dependencies {
+            let Some(original_id) = dependency.original_id else {
+                continue;
+            };
+
+            if address != original_id && Some(address) != dependency.published_at {
+                continue;
+            }
+
+            conflicts.push(DependencyConflict {
+                kind: ConflictKind::NamedAddress,
+                original_id,
+                dependencies: vec![dependency.alias.clone(), name.clone()],
+                message: format!(
+                    "The named address {} is set to {}, which is the package of {}",
+                    name, address, dependency.alias
+                ),
+            });
+        }
+    }
+
+    conflicts
+}
+
+fn package_addresses(package: SuiCachePackageResponse) -> (Option<Address>, Option<Address>) {
+    (Some(package.original_id), Some(package.published_at))
+}
+
+fn is_framework_dependency(source: &DependencySource) -> bool {
+    matches!(source, DependencySource::Git(git_info)
+        if git_info.repository_url.contains(SUI_REPOSITORY)
+            && git_info.path.starts_with(SUI_FRAMEWORK_DIR))
+}
+
+fn describe_git_dependency(git_info: &SafeGitInfo) -> String {
+    let mut description = format!("git {}", git_info.repository_url);
+
+    if !git_info.tag.is_empty() {
+        description.push_str(&format!(" (rev {})", git_info.tag));
+    }
+
+    if !git_info.path.is_empty() {
+        description.push_str(&format!(" ({})", git_info.path));
+    }
+
+    description
+}
+
+/// Reads the addresses of a local package on `network` from (in order):
+/// - its `Published.toml` (`[published.<network>]`),
+/// - its `Move.lock` (`[env.<network>]`),
+/// - its `Move.toml` (`published-at`, and the named address of the package).
+fn local_package_addresses(
+    dir: &Path,
+    network: &Network,
+) -> Result<(Option<Address>, Option<Address>)> {
+    let manifest = read_toml(&dir.join("Move.toml"))
+        .ok_or_else(|| CliError::MissingTomlFile(dir.display().to_string()))?;
+
+    let network = network.to_string();
+
+    if let Some(published) = read_toml(&dir.join("Published.toml"))
+        .and_then(|doc| doc.get("published")?.get(&network).cloned())
+    {
+        return Ok((
+            toml_address(&published, "original-id"),
+            toml_address(&published, PUBLISHED_AT_KEY),
+        ));
+    }
+
+    if let Some(env) =
+        read_toml(&dir.join("Move.lock")).and_then(|doc| doc.get("env")?.get(&network).cloned())
+    {
+        return Ok((
+            toml_address(&env, "original-published-id"),
+            toml_address(&env, "latest-published-id"),
+        ));
+    }
+
+    let package = manifest.get("package");
+    let published_at = package.and_then(|package| toml_address(package, PUBLISHED_AT_KEY));
+    let original_id = package
+        .and_then(|package| package.get("name")?.as_str())
+        .and_then(|name| toml_address(manifest.get(ADDRESSES_KEY)?, name));
+
+    if published_at.is_none() && original_id.is_none() {
+        bail!("The local package at {} is not published", dir.display());
+    }
+
+    Ok((original_id, published_at))
+}
+
+fn read_toml(path: &Path) -> Option<DocumentMut> {
+    fs::read_to_string(path).ok()?.parse().ok()
+}
+
+/// Reads an address, ignoring unpublished (`0x0`) addresses.
+fn toml_address(item: &Item, key: &str) -> Option<Address> {
+    Address::from_str(item.get(key)?.as_str()?)
+        .ok()
+        .filter(|address| *address != Address::ZERO)
+}
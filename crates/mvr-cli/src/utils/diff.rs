@@ -0,0 +1,38 @@
+use similar::TextDiff;
+use yansi::Paint;
+
+/// Returns a unified diff between the `before` and `after` contents of a file,
+/// or `None` if both contents are identical.
+pub fn unified_diff(before: &str, after: &str, path: &str) -> Option<String> {
+    if before == after {
+        return None;
+    }
+
+    let diff = TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string();
+
+    Some(diff)
+}
+
+/// Colors a unified diff for terminal output (additions in green, removals in red).
+pub fn colorize_diff(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            if line.starts_with("+++") || line.starts_with("---") {
+                line.bold().to_string()
+            } else if line.starts_with('+') {
+                line.green().to_string()
+            } else if line.starts_with('-') {
+                line.red().to_string()
+            } else if line.starts_with("@@") {
+                line.blue().to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,304 @@
+use std::env;
+use std::str::FromStr;
+
+use anyhow::Error;
+
+use crate::constants::{EnvVariables, MINIMUM_BUILD_SUI_VERSION};
+use crate::errors::CliError;
+use crate::types::api_data::{api_client, get_api_url, query_package};
+use crate::types::{CheckStatus, DoctorCheck, DoctorReport, Network};
+use crate::utils::config::{config_path_display, MvrConfig};
+use crate::utils::manifest::MoveToml;
+use crate::utils::package_cache::is_offline_mode;
+use crate::utils::sui_binary::{
+    cache_package, is_supported_version, parse_sui_version, sui_binary_report, SuiBinaryReport,
+};
+
+/// Runs the checks behind the `doctor` command. A failing check does not stop the others,
+/// unless they depend on it (e.g. the API can only be checked once we know the network).
+///
+/// The network defaults to the active network of the Sui CLI, and the package used to check
+/// `sui move cache-package` to the first MVR dependency of the Move.toml in the current directory.
+pub async fn run_checks(network: Option<Network>, name: Option<String>) -> DoctorReport {
+    let sui_binary = sui_binary_report();
+
+    let mut checks = vec![check_sui_binary(&sui_binary)];
+
+    if let Ok(report) = &sui_binary {
+        checks.push(check_sui_version(report));
+    }
+
+    checks.push(check_config());
+
+    let network = match network {
+        Some(network) => {
+            let message = format!("{} (from --network)", network);
+            checks.push(check("Network", CheckStatus::Pass, message, None));
+            Some(network)
+        }
+        None => {
+            let (check, network) = check_active_network(sui_binary.as_ref().ok());
+            checks.push(check);
+            network
+        }
+    };
+
+    if let Some(network) = network {
+        checks.push(check_api(&network).await);
+        checks.push(check_cache_package(&network, name).await);
+    }
+
+    DoctorReport {
+        sui_binary: sui_binary.ok(),
+        checks,
+    }
+}
+
+fn check_sui_binary(sui_binary: &Result<SuiBinaryReport, Error>) -> DoctorCheck {
+    match sui_binary {
+        Ok(report) => {
+            let path = report
+                .path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or(report.binary.clone());
+            check("Sui binary", CheckStatus::Pass, path, None)
+        }
+        Err(e) => check(
+            "Sui binary",
+            CheckStatus::Fail,
+            e.to_string(),
+            Some(format!(
+                "Install the Sui CLI, or set `{}` to the path of the `sui` binary.",
+                EnvVariables::SuiBinaryPath
+            )),
+        ),
+    }
+}
+
+fn check_sui_version(report: &SuiBinaryReport) -> DoctorCheck {
+    let supported = parse_sui_version(&report.version)
+        .map(|version| is_supported_version(&version, &MINIMUM_BUILD_SUI_VERSION))
+        .unwrap_or(false);
+
+    if supported {
+        return check(
+            "Sui version",
+            CheckStatus::Pass,
+            report.version.clone(),
+            None,
+        );
+    }
+
+    check(
+        "Sui version",
+        CheckStatus::Fail,
+        format!(
+            "{} (mvr requires at least {})",
+            report.version, MINIMUM_BUILD_SUI_VERSION
+        ),
+        Some(format!(
+            "Upgrade the Sui CLI to version {} or later.",
+            MINIMUM_BUILD_SUI_VERSION
+        )),
+    )
+}
+
+fn check_config() -> DoctorCheck {
+    let path = config_path_display();
+
+    match MvrConfig::load() {
+        Ok(config) => {
+            let networks = config.networks.keys().cloned().collect::<Vec<_>>();
+            let message = if networks.is_empty() {
+                "No custom networks configured".to_string()
+            } else {
+                format!("Custom networks: {}", networks.join(", "))
+            };
+            check("Config", CheckStatus::Pass, message, None)
+        }
+        Err(e) => check(
+            "Config",
+            CheckStatus::Fail,
+            format!("{:#}", e),
+            Some(format!(
+                "Fix or remove {} (or point `{}` to another file).",
+                path,
+                EnvVariables::MvrConfig
+            )),
+        ),
+    }
+}
+
+/// Mirrors `get_active_network`, explaining why the active network could not be used.
+fn check_active_network(sui_binary: Option<&SuiBinaryReport>) -> (DoctorCheck, Option<Network>) {
+    let chain_id = sui_binary.and_then(|report| report.chain_id.clone());
+
+    let reason = match &chain_id {
+        Some(chain_id) => match Network::try_from_chain_identifier(chain_id) {
+            Ok(network) => {
+                let message = format!("{} (chain identifier {})", network, chain_id);
+                return (
+                    check("Network", CheckStatus::Pass, message, None),
+                    Some(network),
+                );
+            }
+            Err(_) => format!(
+                "The chain identifier {} of the active Sui environment is not supported",
+                chain_id
+            ),
+        },
+        None => "Could not detect the active Sui environment".to_string(),
+    };
+
+    let fallback_variable = EnvVariables::MvrFallbackNetwork.to_string();
+
+    let Ok(fallback) = env::var(&fallback_variable) else {
+        return (
+            check(
+                "Network",
+                CheckStatus::Fail,
+                reason,
+                Some(format!(
+                    "Switch to mainnet or testnet (`sui client switch --env <env>`), add the chain to {}, or set `{}`.",
+                    config_path_display(),
+                    fallback_variable
+                )),
+            ),
+            None,
+        );
+    };
+
+    match Network::from_str(&fallback) {
+        Ok(network) => (
+            check(
+                "Network",
+                CheckStatus::Warn,
+                format!("{}, using {} from `{}`", reason, network, fallback_variable),
+                Some(format!(
+                    "Switch to a supported Sui environment, or add the chain to {}.",
+                    config_path_display()
+                )),
+            ),
+            Some(network),
+        ),
+        Err(_) => (
+            check(
+                "Network",
+                CheckStatus::Fail,
+                format!(
+                    "{}, and `{}` ({}) is not a valid network",
+                    reason, fallback_variable, fallback
+                ),
+                Some(format!(
+                    "Set `{}` to mainnet, testnet or a network configured in {}.",
+                    fallback_variable,
+                    config_path_display()
+                )),
+            ),
+            None,
+        ),
+    }
+}
+
+async fn check_api(network: &Network) -> DoctorCheck {
+    let api_url = match get_api_url(network) {
+        Ok(api_url) => api_url,
+        Err(e) => {
+            return check(
+                "MVR API",
+                CheckStatus::Fail,
+                e.to_string(),
+                Some(format!(
+                    "Set the `api_url` of {} in {}.",
+                    network,
+                    config_path_display()
+                )),
+            )
+        }
+    };
+
+    if is_offline_mode() {
+        let message = format!("{} (not checked in offline mode)", api_url);
+        return check("MVR API", CheckStatus::Warn, message, None);
+    }
+
+    let fix = Some(format!(
+        "Check your connection to {}, or the `api_url` configured for {}.",
+        api_url, network
+    ));
+
+    let health: Result<(), Error> = async { Ok(api_client(network)?.health().await?) }.await;
+
+    match health {
+        Ok(()) => check("MVR API", CheckStatus::Pass, api_url, None),
+        Err(e) => check("MVR API", CheckStatus::Fail, e.to_string(), fix),
+    }
+}
+
+async fn check_cache_package(network: &Network, name: Option<String>) -> DoctorCheck {
+    let name = name.or_else(|| {
+        let move_toml = MoveToml::new(env::current_dir().ok()?.join("Move.toml")).ok()?;
+        move_toml
+            .get_mvr_dependencies()
+            .first()
+            .map(|dependency| dependency.name.clone())
+    });
+
+    let Some(name) = name else {
+        return check(
+            "Cache package",
+            CheckStatus::Warn,
+            "No MVR name to check".to_string(),
+            Some(
+                "Pass a name with `--name`, or run `mvr doctor` in a Move package with MVR dependencies."
+                    .to_string(),
+            ),
+        );
+    };
+
+    let package = match query_package(&name, network).await {
+        Ok((_, package)) => package,
+        Err(e) => {
+            return check(
+                "Cache package",
+                CheckStatus::Fail,
+                format!("Could not resolve {}: {}", name, e),
+                Some(format!(
+                    "Check that {} exists on {} (`mvr resolve {} --network {}`).",
+                    name, network, name, network
+                )),
+            )
+        }
+    };
+
+    match cache_package(package, network) {
+        Ok(response) => check(
+            "Cache package",
+            CheckStatus::Pass,
+            format!("Cached {} (published at {})", name, response.published_at),
+            None,
+        ),
+        Err(e) => {
+            let fix = match e.downcast_ref::<CliError>() {
+                Some(CliError::UnexpectedParsing(_)) => {
+                    "`sui move cache-package` produced unexpected output. Make sure your Sui CLI is up to date.".to_string()
+                }
+                _ => format!(
+                    "Make sure git can access the repository of {}, and that your Sui CLI supports `sui move cache-package`.",
+                    name
+                ),
+            };
+            check("Cache package", CheckStatus::Fail, e.to_string(), Some(fix))
+        }
+    }
+}
+
+fn check(name: &str, status: CheckStatus, message: String, fix: Option<String>) -> DoctorCheck {
+    DoctorCheck {
+        name: name.to_string(),
+        status,
+        message,
+        fix,
+    }
+}
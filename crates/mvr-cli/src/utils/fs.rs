@@ -0,0 +1,32 @@
+use std::{fs, io::Write, path::Path};
+
+use anyhow::{Context, Result};
+
+/// Writes to a temporary file in the same directory as `path` (creating it if needed), syncs it
+/// to disk and renames it over `path`, keeping the permissions of the existing file.
+///
+/// Readers (e.g. parallel builds) never observe a partially written file, and an interrupted
+/// write leaves the previous content untouched.
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+
+    let mut temp_file = tempfile::NamedTempFile::new_in(dir)?;
+    temp_file.write_all(content)?;
+    temp_file.as_file().sync_all()?;
+
+    if let Ok(metadata) = fs::metadata(path) {
+        fs::set_permissions(temp_file.path(), metadata.permissions())?;
+    }
+
+    temp_file
+        .persist(path)
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+
+    Ok(())
+}
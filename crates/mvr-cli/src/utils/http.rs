@@ -0,0 +1,53 @@
+use std::{env, sync::OnceLock, time::Duration};
+
+use mvr_client::MVR_SOURCE_HEADER;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client,
+};
+
+use crate::constants::EnvVariables;
+
+const MVR_SOURCE: &str = concat!("mvr-cli/", env!("CARGO_PKG_VERSION"));
+
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Returns the HTTP client shared by all requests to the MVR API.
+///
+/// Timeouts can be configured with `MVR_CONNECT_TIMEOUT_SECS` and `MVR_REQUEST_TIMEOUT_SECS`.
+pub fn client() -> &'static Client {
+    CLIENT.get_or_init(|| {
+        let mut headers = HeaderMap::new();
+        headers.insert(MVR_SOURCE_HEADER, HeaderValue::from_static(MVR_SOURCE));
+
+        Client::builder()
+            .default_headers(headers)
+            .connect_timeout(Duration::from_secs(env_or(
+                EnvVariables::MvrConnectTimeoutSecs,
+                DEFAULT_CONNECT_TIMEOUT_SECS,
+            )))
+            .timeout(Duration::from_secs(env_or(
+                EnvVariables::MvrRequestTimeoutSecs,
+                DEFAULT_REQUEST_TIMEOUT_SECS,
+            )))
+            .build()
+            .expect("[mvr invariant] The HTTP client configuration is valid.")
+    })
+}
+
+/// The number of times requests to the MVR API are retried (`MVR_MAX_RETRIES`, or 3 by default).
+/// Retries are done by the MVR API client (see `mvr_client::send_with_retries`).
+pub fn max_retries() -> u32 {
+    env_or(EnvVariables::MvrMaxRetries, DEFAULT_MAX_RETRIES)
+}
+
+pub(crate) fn env_or<T: std::str::FromStr>(variable: EnvVariables, default: T) -> T {
+    env::var(variable.to_string())
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
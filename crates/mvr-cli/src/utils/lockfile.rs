@@ -0,0 +1,107 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sui_sdk_types::Address;
+
+use crate::{
+    types::{api_types::SafeGitInfo, Network},
+    utils::fs::write_atomic,
+};
+
+pub const MVR_LOCK_FILE: &str = "Mvr.lock";
+
+const LOCK_FILE_VERSION: u64 = 1;
+const LOCK_FILE_HEADER: &str =
+    "# @generated by mvr. This file records the resolved MVR dependencies. Do not edit it manually.\n\n";
+
+/// The `Mvr.lock` file, which pins every resolved MVR name (per network) to the exact
+/// package version, addresses and git source it resolved to, so builds are reproducible.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MvrLock {
+    pub version: u64,
+    /// network -> versioned name (as requested, e.g. `@org/app` or `@org/app/3`) -> locked package.
+    #[serde(default)]
+    pub env: BTreeMap<String, BTreeMap<String, LockedPackage>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+    /// The version of the package the name resolved to.
+    pub version: u64,
+    pub package_address: String,
+    pub original_id: Address,
+    pub git: SafeGitInfo,
+}
+
+impl Default for MvrLock {
+    fn default() -> Self {
+        Self {
+            version: LOCK_FILE_VERSION,
+            env: BTreeMap::new(),
+        }
+    }
+}
+
+impl MvrLock {
+    /// Returns the path of the lock file for a package directory.
+    pub fn path_for(package_dir: &Path) -> PathBuf {
+        package_dir.join(MVR_LOCK_FILE)
+    }
+
+    /// Reads the lock file from disk. A missing file is treated as an empty lock.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read lock file: {}", path.display()))?;
+
+        let lock: Self = toml_edit::de::from_str(&content)
+            .with_context(|| format!("Failed to parse lock file: {}", path.display()))?;
+
+        if lock.version != LOCK_FILE_VERSION {
+            bail!(
+                "Unsupported {} version {} (expected {}). Please install the latest version of the CLI.",
+                MVR_LOCK_FILE,
+                lock.version,
+                LOCK_FILE_VERSION
+            );
+        }
+
+        Ok(lock)
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        let content = format!(
+            "{}{}",
+            LOCK_FILE_HEADER,
+            toml_edit::ser::to_string_pretty(self)?
+        );
+
+        // The lock is replaced atomically, so an interrupted build never leaves it truncated.
+        write_atomic(path, content.as_bytes())
+            .with_context(|| format!("Failed to write lock file: {}", path.display()))
+    }
+
+    pub fn get(&self, network: &Network, name: &str) -> Option<&LockedPackage> {
+        self.env.get(&network.to_string())?.get(name)
+    }
+
+    /// Records a resolved package. Returns true if the lock changed.
+    pub fn insert(&mut self, network: &Network, name: &str, package: LockedPackage) -> bool {
+        let packages = self.env.entry(network.to_string()).or_default();
+
+        if packages.get(name) == Some(&package) {
+            return false;
+        }
+
+        packages.insert(name.to_string(), package);
+        true
+    }
+}
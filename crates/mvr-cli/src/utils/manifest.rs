@@ -1,21 +1,55 @@
-use std::{fs, path::PathBuf, str::FromStr};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use yansi::Paint;
 
 use anyhow::{bail, Context, Result};
+use mvr_types::name::VersionedName;
 use toml_edit::{DocumentMut, Formatted, InlineTable, Item, Table, TableLike, Value};
 
+use crate::errors::CliError;
+use crate::types::api_types::SafeGitInfo;
+use crate::types::{MvrDependency, WriteOptions};
+use crate::utils::diff::unified_diff;
+use crate::utils::fs::write_atomic;
 use crate::MoveRegistryDependencies;
 
 pub const RESOLVER_PREFIX_KEY: &str = "r";
 pub const MVR_RESOLVER_KEY: &str = "mvr";
 pub const NETWORK_KEY: &str = "network";
+pub const NAME_KEY: &str = "name";
 pub const DEPENDENCIES_KEY: &str = "dependencies";
 pub const PUBLISHED_AT_KEY: &str = "published-at";
 pub const ADDRESSES_KEY: &str = "addresses";
 
+pub const GIT_KEY: &str = "git";
+pub const REV_KEY: &str = "rev";
+pub const SUBDIR_KEY: &str = "subdir";
+pub const LOCAL_KEY: &str = "local";
+
+/// Where a dependency of the `[dependencies]` table comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySource {
+    /// An MVR name (`r.mvr`).
+    Mvr(String),
+    Git(SafeGitInfo),
+    /// A local path, relative to the `Move.toml` file.
+    Local(PathBuf),
+    /// Any other dependency (e.g. another external resolver).
+    Other,
+}
+
+/// The extension of the backups kept when saving with `WriteOptions::backup` (`Move.toml.bak`).
+pub const BACKUP_EXTENSION: &str = "bak";
+
 pub struct MoveToml {
     pub path: Option<PathBuf>,
     pub doc: DocumentMut,
+    /// The document as it was read, to diff the changes against.
+    original: String,
 }
 
 impl MoveToml {
@@ -24,6 +58,7 @@ impl MoveToml {
         let doc = read_package_toml_file(&path)?;
         Ok(Self {
             path: Some(path),
+            original: doc.to_string(),
             doc,
         })
     }
@@ -31,26 +66,159 @@ impl MoveToml {
     /// Creates a new `MoveToml` instance from a passed `Move.toml` file content.
     pub fn new_from_content(content: &str, path: Option<PathBuf>) -> Result<Self> {
         let doc = toml_edit::DocumentMut::from_str(content)?;
-        Ok(Self { path, doc })
+        Ok(Self {
+            path,
+            original: doc.to_string(),
+            doc,
+        })
     }
 
-    /// Adds a dependency to the `[dependencies]` table of the `Move.toml` file.
-    pub fn add_dependency(&mut self, name: &str, mvr_name: &str) -> Result<()> {
+    /// Adds a dependency to the `[dependencies]` table of the `Move.toml` file, under its alias.
+    ///
+    /// A different dependency (MVR or not) with the same key is only replaced with `force`.
+    pub fn add_dependency(&mut self, dependency: &MvrDependency, force: bool) -> Result<()> {
         self.create_deps_if_not_exists()?;
 
         // SAFETY: `ensure_dependencies_table_exists` ensures that the `[dependencies]` table exists.
         let dependencies = self.doc[DEPENDENCIES_KEY].as_table_mut().unwrap();
 
+        if let Some(existing) = dependencies.get(&dependency.alias) {
+            let existing = existing
+                .as_table_like()
+                .and_then(|table| Some((find_mvr_package(table)?, find_mvr_network(table))));
+
+            if !force && existing != Some((dependency.name.clone(), dependency.network.clone())) {
+                bail!(CliError::DependencyAlreadyExists(
+                    dependency.alias.clone(),
+                    self.path
+                        .as_ref()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or("Move.toml".to_string())
+                ));
+            }
+        }
+
+        let name = Value::String(Formatted::new(dependency.name.clone()));
+
+        // A network override turns `r.mvr` into a (dotted) table: `r.mvr.name`, `r.mvr.network`.
+        let mvr_value = match &dependency.network {
+            Some(network) => {
+                let mut mvr_table = InlineTable::new();
+                mvr_table.set_dotted(true);
+                mvr_table.insert(NAME_KEY, name);
+                mvr_table.insert(NETWORK_KEY, Value::String(Formatted::new(network.clone())));
+                Value::InlineTable(mvr_table)
+            }
+            None => name,
+        };
+
         let mut new_dep_table = InlineTable::new();
         let mut r_table = InlineTable::new();
         r_table.set_dotted(true); // our `r.mvr` is a dotted table
-        r_table.insert(
-            MVR_RESOLVER_KEY,
-            Value::String(Formatted::new(mvr_name.to_string())),
-        );
+        r_table.insert(MVR_RESOLVER_KEY, mvr_value);
         new_dep_table.insert(RESOLVER_PREFIX_KEY, Value::InlineTable(r_table));
 
-        dependencies.insert(name, Item::Value(Value::InlineTable(new_dep_table)));
+        dependencies.insert(
+            &dependency.alias,
+            Item::Value(Value::InlineTable(new_dep_table)),
+        );
+
+        Ok(())
+    }
+
+    /// Removes an MVR dependency from the `[dependencies]` table.
+    ///
+    /// The dependency is matched either by its key (alias) or by its `r.mvr` name.
+    /// An unversioned name (e.g. `@org/app`) also matches pinned entries (e.g. `@org/app/3`).
+    /// Non-MVR dependencies are never removed.
+    ///
+    /// Fails (without removing anything) if a name matches several dependencies, e.g. `@org/app`
+    /// when both `@org/app/1` and `@org/app/2` are used under different aliases.
+    pub fn remove_dependency(&mut self, name: &str) -> Result<Option<MvrDependency>> {
+        let Some(dependencies) = self
+            .doc
+            .get_mut(DEPENDENCIES_KEY)
+            .and_then(|v| v.as_table_like_mut())
+        else {
+            return Ok(None);
+        };
+
+        let requested_name = VersionedName::from_str(name).ok();
+
+        let mut matches = dependencies
+            .iter()
+            .filter_map(|(key, value)| {
+                let mvr_name = value.as_table_like().and_then(find_mvr_package)?;
+
+                let matches_name = mvr_name == name
+                    || requested_name.as_ref().is_some_and(|requested| {
+                        requested.version.is_none()
+                            && VersionedName::from_str(&mvr_name)
+                                .is_ok_and(|dep_name| dep_name.name == requested.name)
+                    });
+
+                (key == name || matches_name).then(|| MvrDependency {
+                    alias: key.to_string(),
+                    name: mvr_name,
+                    network: value.as_table_like().and_then(find_mvr_network),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Aliases are unique, so an alias always refers to a single dependency.
+        if let Some(index) = matches.iter().position(|dep| dep.alias == name) {
+            matches = vec![matches.swap_remove(index)];
+        }
+
+        if matches.len() > 1 {
+            let aliases = matches
+                .iter()
+                .map(|dep| format!("{} ({})", dep.alias, dep.name))
+                .collect::<Vec<_>>();
+            bail!(CliError::AmbiguousDependency(
+                name.to_string(),
+                aliases.join(", ")
+            ));
+        }
+
+        let removed = matches.pop();
+
+        if let Some(dependency) = &removed {
+            dependencies.remove(&dependency.alias);
+        }
+
+        Ok(removed)
+    }
+
+    /// Rewrites the MVR name of the dependency under `alias` (its `r.mvr` or `r.mvr.name` value),
+    /// keeping the rest of the dependency (and its formatting) untouched.
+    pub fn set_dependency_name(&mut self, alias: &str, name: &str) -> Result<()> {
+        let mvr = self
+            .doc
+            .get_mut(DEPENDENCIES_KEY)
+            .and_then(|deps| deps.as_table_like_mut())
+            .and_then(|deps| deps.get_mut(alias))
+            .and_then(|dep| dep.as_table_like_mut())
+            .and_then(|dep| dep.get_mut(RESOLVER_PREFIX_KEY))
+            .and_then(|r| r.as_table_like_mut())
+            .and_then(|r| r.get_mut(MVR_RESOLVER_KEY));
+
+        let value = match mvr {
+            Some(mvr) if mvr.is_str() => mvr.as_value_mut(),
+            Some(mvr) => mvr
+                .as_table_like_mut()
+                .and_then(|mvr| mvr.get_mut(NAME_KEY))
+                .and_then(|name| name.as_value_mut()),
+            None => None,
+        };
+
+        let Some(value) = value.filter(|value| value.is_str()) else {
+            bail!(CliError::DependencyNotFound(alias.to_string()));
+        };
+
+        let decor = value.decor().clone();
+        *value = Value::from(name);
+        *value.decor_mut() = decor;
 
         Ok(())
     }
@@ -65,13 +233,54 @@ impl MoveToml {
             .map(|s| s.to_string())
     }
 
+    /// Returns the unified diff of the changes made since the file was read, or `None` if
+    /// there are none. The path in the diff is relative to the current directory, if possible.
+    pub fn diff(&self) -> Option<String> {
+        let path = match &self.path {
+            Some(path) => env::current_dir()
+                .ok()
+                .and_then(|dir| path.strip_prefix(dir).ok())
+                .unwrap_or(path)
+                .display()
+                .to_string(),
+            None => "Move.toml".to_string(),
+        };
+
+        unified_diff(&self.original, &self.doc.to_string(), &path)
+    }
+
+    /// Writes the changes made since the file was read (if any), unless in `dry_run` mode.
+    /// Returns the diff of the changes.
+    pub fn write_changes(&self, options: &WriteOptions) -> Result<Option<String>> {
+        let diff = self.diff();
+
+        if diff.is_some() && !options.dry_run {
+            self.save_to_file(options.backup)?;
+        }
+
+        Ok(diff)
+    }
+
     /// Writes the state of `MoveToml` file back to the file system (replaces the initial state).
-    pub fn save_to_file(&self) -> Result<()> {
+    ///
+    /// The file is replaced atomically, so it is never left partially written. With `backup`,
+    /// the previous file is kept as `Move.toml.bak`.
+    pub fn save_to_file(&self, backup: bool) -> Result<()> {
         let Some(path) = &self.path else {
             bail!("This `MoveToml` instance is created in read-only mode.");
         };
 
-        fs::write(path.clone(), self.doc.to_string()).with_context(|| {
+        if backup && path.exists() {
+            let backup_path = backup_path(path);
+            fs::copy(path, &backup_path).with_context(|| {
+                format!(
+                    "Failed to back up TOML file to: {:?}",
+                    backup_path.display()
+                )
+            })?;
+        }
+
+        write_atomic(path, self.doc.to_string().as_bytes()).with_context(|| {
             format!("Failed to write updated TOML to file: {:?}", path.display())
         })?;
 
@@ -104,6 +313,64 @@ impl MoveToml {
         Ok(MoveRegistryDependencies { packages })
     }
 
+    /// Returns all MVR dependencies (entries with an `r.mvr` value) of the `[dependencies]` table,
+    /// in the order they are declared. Returns an empty list if there are no dependencies.
+    pub fn get_mvr_dependencies(&self) -> Vec<MvrDependency> {
+        let Some(dependencies) = self
+            .doc
+            .get(DEPENDENCIES_KEY)
+            .and_then(|v| v.as_table_like())
+        else {
+            return vec![];
+        };
+
+        dependencies
+            .iter()
+            .filter_map(|(key, value)| {
+                let mvr_name = value.as_table_like().and_then(find_mvr_package)?;
+                Some(MvrDependency {
+                    alias: key.to_string(),
+                    name: mvr_name,
+                    network: value.as_table_like().and_then(find_mvr_network),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns every dependency of the `[dependencies]` table (MVR or not), along with its source.
+    pub fn get_dependency_sources(&self) -> Vec<(String, DependencySource)> {
+        let Some(dependencies) = self
+            .doc
+            .get(DEPENDENCIES_KEY)
+            .and_then(|v| v.as_table_like())
+        else {
+            return vec![];
+        };
+
+        dependencies
+            .iter()
+            .map(|(key, value)| {
+                let source = value
+                    .as_table_like()
+                    .map(dependency_source)
+                    .unwrap_or(DependencySource::Other);
+                (key.to_string(), source)
+            })
+            .collect()
+    }
+
+    /// Returns the named addresses of the `[addresses]` table that are set to an address.
+    pub fn get_addresses(&self) -> BTreeMap<String, String> {
+        let Some(addresses) = self.doc.get(ADDRESSES_KEY).and_then(|v| v.as_table_like()) else {
+            return BTreeMap::new();
+        };
+
+        addresses
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.as_str()?.to_string())))
+            .collect()
+    }
+
     pub fn get_dependencies(&self) -> Result<&Table> {
         self.doc
             .get(DEPENDENCIES_KEY)
@@ -120,9 +387,16 @@ impl MoveToml {
     }
 }
 
+/// Returns the path of the backup of a file (e.g. `Move.toml.bak`).
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{BACKUP_EXTENSION}"));
+    path.with_file_name(file_name)
+}
+
 fn read_package_toml_file(path: &PathBuf) -> Result<DocumentMut> {
     if !path.exists() {
-        bail!("Move.toml not found in the current directory".red());
+        bail!(CliError::MoveTomlNotFound(path.display().to_string()));
     }
 
     let toml_content = fs::read_to_string(path.clone()).with_context(|| {
@@ -140,11 +414,48 @@ fn read_package_toml_file(path: &PathBuf) -> Result<DocumentMut> {
     Ok(doc)
 }
 
+fn dependency_source(table: &dyn TableLike) -> DependencySource {
+    if let Some(name) = find_mvr_package(table) {
+        return DependencySource::Mvr(name);
+    }
+
+    let get = |key| table.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    if let Some(repository_url) = get(GIT_KEY) {
+        return DependencySource::Git(SafeGitInfo {
+            repository_url,
+            path: get(SUBDIR_KEY).unwrap_or_default(),
+            tag: get(REV_KEY).unwrap_or_default(),
+        });
+    }
+
+    match get(LOCAL_KEY) {
+        Some(path) => DependencySource::Local(PathBuf::from(path)),
+        None => DependencySource::Other,
+    }
+}
+
 fn find_mvr_package(table: &dyn TableLike) -> Option<String> {
+    let mvr = find_mvr_value(table)?;
+
+    mvr.as_str()
+        .or_else(|| mvr.as_table_like()?.get(NAME_KEY)?.as_str())
+        .map(String::from)
+}
+
+/// The network override of a dependency (its `r.mvr.network` value).
+fn find_mvr_network(table: &dyn TableLike) -> Option<String> {
+    find_mvr_value(table)?
+        .as_table_like()?
+        .get(NETWORK_KEY)?
+        .as_str()
+        .map(String::from)
+}
+
+/// The `r.mvr` value of a dependency: either its name, or a table with its `name` and `network`.
+fn find_mvr_value(table: &dyn TableLike) -> Option<&Item> {
     table
         .get(RESOLVER_PREFIX_KEY)
         .and_then(|r| r.as_table_like())
         .and_then(|r_table| r_table.get(MVR_RESOLVER_KEY))
-        .and_then(|mvr| mvr.as_str())
-        .map(String::from)
 }
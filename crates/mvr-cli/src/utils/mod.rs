@@ -1,2 +1,16 @@
+pub mod config;
+pub mod conflicts;
+pub mod diff;
+pub mod doctor;
+pub mod fs;
+pub mod http;
+pub mod lockfile;
 pub mod manifest;
+pub mod package_cache;
+pub mod paths;
+pub mod resolution_cache;
+pub mod sparkline;
 pub mod sui_binary;
+pub mod sui_fixtures;
+pub mod verify;
+pub mod workspace;
@@ -0,0 +1,197 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    constants::EnvVariables,
+    types::{api_types::PackageRequest, Network},
+    utils::{fs::write_atomic, paths::mvr_home_dir, sui_binary::SuiCachePackageResponse},
+};
+
+const CACHE_DIR: &str = "cache";
+const OBJECTS_DIR: &str = "objects";
+const PACKAGE_REQUESTS_INDEX: &str = "package-requests.json";
+const CACHED_PACKAGES_INDEX: &str = "cached-packages.json";
+
+/// Set through the global `--offline` flag.
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enables (or disables) offline mode for the current process.
+pub fn set_offline_mode(offline: bool) {
+    OFFLINE_MODE.store(offline, Ordering::Relaxed);
+}
+
+/// Returns true if we should only answer from the local cache, either because the `--offline` flag
+/// was passed, or because the `MVR_OFFLINE` environment variable is set to a truthy value.
+pub fn is_offline_mode() -> bool {
+    OFFLINE_MODE.load(Ordering::Relaxed)
+        || env::var(EnvVariables::MvrOffline.to_string())
+            .is_ok_and(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+/// A local, content-addressed cache of resolved packages.
+///
+/// Every cached value is stored once under `objects/<sha256>.json`, and per network we keep
+/// an index from the versioned name (e.g. `@org/app` or `@org/app/3`) to the digest of its value:
+///
+/// ```text
+/// <root>/objects/<sha256>.json
+/// <root>/<network>/package-requests.json  // name -> `PackageRequest` digest
+/// <root>/<network>/cached-packages.json   // name -> `SuiCachePackageResponse` digest
+/// ```
+pub struct PackageCache {
+    root: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CacheIndex {
+    entries: BTreeMap<String, String>,
+}
+
+impl PackageCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// The cache lives under `MVR_CACHE_DIR` if set, or under `~/.mvr/cache` otherwise.
+    pub fn from_env() -> Result<Self> {
+        let root = match env::var_os(EnvVariables::MvrCacheDir.to_string()) {
+            Some(dir) => PathBuf::from(dir),
+            None => mvr_home_dir()?.join(CACHE_DIR),
+        };
+
+        Ok(Self::new(root))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn get_package_request(
+        &self,
+        network: &Network,
+        name: &str,
+    ) -> Result<Option<PackageRequest>> {
+        self.get(network, PACKAGE_REQUESTS_INDEX, name)
+    }
+
+    pub fn put_package_request(
+        &self,
+        network: &Network,
+        name: &str,
+        package: &PackageRequest,
+    ) -> Result<()> {
+        self.put(network, PACKAGE_REQUESTS_INDEX, name, package)
+    }
+
+    /// Cached packages are always keyed by their pinned name (e.g. `@org/app/3`).
+    pub fn get_cached_package(
+        &self,
+        network: &Network,
+        name: &str,
+    ) -> Result<Option<SuiCachePackageResponse>> {
+        self.get(network, CACHED_PACKAGES_INDEX, name)
+    }
+
+    pub fn put_cached_package(
+        &self,
+        network: &Network,
+        name: &str,
+        package: &SuiCachePackageResponse,
+    ) -> Result<()> {
+        self.put(network, CACHED_PACKAGES_INDEX, name, package)
+    }
+
+    /// Removes the whole cache.
+    pub fn clear(&self) -> Result<()> {
+        if !self.root.exists() {
+            return Ok(());
+        }
+
+        fs::remove_dir_all(&self.root)
+            .with_context(|| format!("Failed to remove cache directory: {}", self.root.display()))
+    }
+
+    fn get<T: DeserializeOwned>(
+        &self,
+        network: &Network,
+        index: &str,
+        name: &str,
+    ) -> Result<Option<T>> {
+        let index = self.read_index(network, index)?;
+
+        let Some(digest) = index.entries.get(name) else {
+            return Ok(None);
+        };
+
+        let path = self.object_path(digest);
+
+        // A missing object means the cache was partially cleaned up, which we treat as a cache miss.
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read(&path)
+            .with_context(|| format!("Failed to read cache entry: {}", path.display()))?;
+
+        Ok(Some(serde_json::from_slice(&content).with_context(
+            || format!("Failed to parse cache entry: {}", path.display()),
+        )?))
+    }
+
+    fn put<T: Serialize>(
+        &self,
+        network: &Network,
+        index_name: &str,
+        name: &str,
+        value: &T,
+    ) -> Result<()> {
+        let content = serde_json::to_vec_pretty(value)?;
+        let digest = format!("{:x}", Sha256::digest(&content));
+
+        let object_path = self.object_path(&digest);
+        if !object_path.exists() {
+            write_atomic(&object_path, &content)?;
+        }
+
+        let mut index = self.read_index(network, index_name)?;
+        if index.entries.get(name) == Some(&digest) {
+            return Ok(());
+        }
+        index.entries.insert(name.to_string(), digest);
+
+        write_atomic(
+            &self.index_path(network, index_name),
+            &serde_json::to_vec_pretty(&index)?,
+        )
+    }
+
+    fn read_index(&self, network: &Network, index: &str) -> Result<CacheIndex> {
+        let path = self.index_path(network, index);
+
+        if !path.exists() {
+            return Ok(CacheIndex::default());
+        }
+
+        let content = fs::read(&path)
+            .with_context(|| format!("Failed to read cache index: {}", path.display()))?;
+
+        serde_json::from_slice(&content)
+            .with_context(|| format!("Failed to parse cache index: {}", path.display()))
+    }
+
+    fn index_path(&self, network: &Network, index: &str) -> PathBuf {
+        self.root.join(network.to_string()).join(index)
+    }
+
+    fn object_path(&self, digest: &str) -> PathBuf {
+        self.root.join(OBJECTS_DIR).join(format!("{digest}.json"))
+    }
+}
@@ -0,0 +1,13 @@
+use std::{env, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+
+const MVR_HOME_DIR: &str = ".mvr";
+
+/// Returns the directory where `mvr` keeps its local state (`~/.mvr`).
+pub fn mvr_home_dir() -> Result<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(MVR_HOME_DIR))
+        .ok_or_else(|| anyhow!("Failed to find the home directory of the current user."))
+}
@@ -0,0 +1,298 @@
+use std::{
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use mvr_types::name::VersionedName;
+use serde::{Deserialize, Serialize};
+use sui_sdk_types::Address;
+
+use crate::{
+    constants::EnvVariables,
+    types::{api_types::PackageRequest, CachedResolution, CachedResolutionKind, Network},
+    utils::{fs::write_atomic, http::env_or, package_cache::PackageCache},
+};
+
+const RESOLUTION_CACHE_FILE: &str = "resolution-cache.json";
+const DEFAULT_TTL_SECS: u64 = 300;
+
+/// The cache files loaded by the current process, by path.
+static LOADED: OnceLock<Mutex<HashMap<PathBuf, ResolutionCacheFile>>> = OnceLock::new();
+
+/// A cache of resolution (name -> package) and reverse resolution (package address -> name)
+/// results, kept in memory for the current process and on disk across invocations:
+///
+/// ```text
+/// <root>/<network>/resolution-cache.json
+/// ```
+///
+/// Pinned names (e.g. `@org/app/3`) always resolve to the same package, so they are cached
+/// indefinitely. Unversioned names and package addresses are cached for `ttl`.
+pub struct ResolutionCache {
+    root: PathBuf,
+    ttl: Duration,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+struct ResolutionCacheFile {
+    #[serde(default)]
+    names: BTreeMap<String, CacheEntry<PackageRequest>>,
+    #[serde(default)]
+    addresses: BTreeMap<String, CacheEntry<Option<String>>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct CacheEntry<T> {
+    cached_at: u64,
+    value: T,
+}
+
+impl ResolutionCache {
+    pub fn new(root: PathBuf, ttl: Duration) -> Self {
+        Self { root, ttl }
+    }
+
+    /// Uses the root of the local package cache (see `PackageCache::from_env`), and a TTL of
+    /// `MVR_CACHE_TTL_SECS` seconds (5 minutes by default).
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(
+            PackageCache::from_env()?.root().to_path_buf(),
+            Duration::from_secs(env_or(EnvVariables::MvrCacheTtlSecs, DEFAULT_TTL_SECS)),
+        ))
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn get_package(
+        &self,
+        network: &Network,
+        name: &VersionedName,
+    ) -> Result<Option<PackageRequest>> {
+        let now = now();
+
+        self.with_file(network, |file| {
+            file.names
+                .get(&name.to_string())
+                .filter(|entry| self.is_fresh(entry, name.version.is_some(), now))
+                .map(|entry| entry.value.clone())
+        })
+    }
+
+    pub fn put_packages<'a>(
+        &self,
+        network: &Network,
+        packages: impl IntoIterator<Item = (&'a VersionedName, &'a PackageRequest)>,
+    ) -> Result<()> {
+        let cached_at = now();
+
+        self.update_file(network, |file| {
+            for (name, package) in packages {
+                file.names.insert(
+                    name.to_string(),
+                    CacheEntry {
+                        cached_at,
+                        value: package.clone(),
+                    },
+                );
+            }
+        })
+    }
+
+    /// Returns the cached name of a package address: `Some(None)` if the address is cached
+    /// as not having a name.
+    pub fn get_name(&self, network: &Network, address: &Address) -> Result<Option<Option<String>>> {
+        let now = now();
+
+        self.with_file(network, |file| {
+            file.addresses
+                .get(&address.to_string())
+                .filter(|entry| self.is_fresh(entry, false, now))
+                .map(|entry| entry.value.clone())
+        })
+    }
+
+    pub fn put_names<'a>(
+        &self,
+        network: &Network,
+        names: impl IntoIterator<Item = (&'a Address, &'a Option<String>)>,
+    ) -> Result<()> {
+        let cached_at = now();
+
+        self.update_file(network, |file| {
+            for (address, name) in names {
+                file.addresses.insert(
+                    address.to_string(),
+                    CacheEntry {
+                        cached_at,
+                        value: name.clone(),
+                    },
+                );
+            }
+        })
+    }
+
+    /// Lists the cached results of a network (or of all networks), including expired ones
+    /// that were not cleaned up yet.
+    pub fn entries(&self, network: Option<&Network>) -> Result<Vec<CachedResolution>> {
+        let mut entries = vec![];
+
+        for network in self.networks(network)? {
+            let file = read_file(&self.file_path(&network))?;
+
+            entries.extend(file.names.iter().map(|(name, entry)| {
+                let pinned = VersionedName::from_str(name).is_ok_and(|n| n.version.is_some());
+                CachedResolution {
+                    network: network.clone(),
+                    kind: CachedResolutionKind::Resolution,
+                    key: name.clone(),
+                    value: Some(entry.value.package_address.clone()),
+                    cached_at: entry.cached_at,
+                    expires_at: (!pinned).then(|| self.expires_at(entry)),
+                }
+            }));
+
+            entries.extend(
+                file.addresses
+                    .iter()
+                    .map(|(address, entry)| CachedResolution {
+                        network: network.clone(),
+                        kind: CachedResolutionKind::ReverseResolution,
+                        key: address.clone(),
+                        value: entry.value.clone(),
+                        cached_at: entry.cached_at,
+                        expires_at: Some(self.expires_at(entry)),
+                    }),
+            );
+        }
+
+        Ok(entries)
+    }
+
+    /// Removes the cached results of a network (or of all networks), returning how many
+    /// results were removed.
+    pub fn clear(&self, network: Option<&Network>) -> Result<usize> {
+        let mut removed = 0;
+        let mut loaded = loaded().lock().unwrap();
+
+        for network in self.networks(network)? {
+            let path = self.file_path(&network);
+            let file = read_file(&path)?;
+            removed += file.names.len() + file.addresses.len();
+
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove cache file: {}", path.display()))?;
+            loaded.remove(&path);
+        }
+
+        Ok(removed)
+    }
+
+    /// The networks (by name) with a cache file, out of `network` (if given) or all networks.
+    fn networks(&self, network: Option<&Network>) -> Result<Vec<String>> {
+        let mut names = match network {
+            Some(network) => vec![network.to_string()],
+            None if !self.root.exists() => vec![],
+            None => fs::read_dir(&self.root)
+                .with_context(|| {
+                    format!("Failed to read cache directory: {}", self.root.display())
+                })?
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .collect(),
+        };
+
+        names.retain(|name| self.file_path(name).exists());
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Runs `f` on the cache file of a network, loading it from disk on first use.
+    fn with_file<T>(
+        &self,
+        network: &Network,
+        f: impl FnOnce(&ResolutionCacheFile) -> T,
+    ) -> Result<T> {
+        let path = self.file_path(&network.to_string());
+        let mut loaded = loaded().lock().unwrap();
+
+        let file = match loaded.entry(path) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let file = read_file(entry.key())?;
+                entry.insert(file)
+            }
+        };
+
+        Ok(f(file))
+    }
+
+    /// Updates the cache file of a network, dropping expired results. The file is re-read
+    /// from disk first, so results cached by other processes in the meantime are kept.
+    fn update_file(
+        &self,
+        network: &Network,
+        f: impl FnOnce(&mut ResolutionCacheFile),
+    ) -> Result<()> {
+        let path = self.file_path(&network.to_string());
+        let mut loaded = loaded().lock().unwrap();
+
+        let mut file = read_file(&path)?;
+        f(&mut file);
+
+        let now = now();
+        file.names.retain(|name, entry| {
+            let pinned = VersionedName::from_str(name).is_ok_and(|n| n.version.is_some());
+            self.is_fresh(entry, pinned, now)
+        });
+        file.addresses
+            .retain(|_, entry| self.is_fresh(entry, false, now));
+
+        write_atomic(&path, &serde_json::to_vec_pretty(&file)?)?;
+        loaded.insert(path, file);
+
+        Ok(())
+    }
+
+    fn is_fresh<T>(&self, entry: &CacheEntry<T>, pinned: bool, now: u64) -> bool {
+        pinned || now.saturating_sub(entry.cached_at) < self.ttl.as_secs()
+    }
+
+    fn expires_at<T>(&self, entry: &CacheEntry<T>) -> u64 {
+        entry.cached_at.saturating_add(self.ttl.as_secs())
+    }
+
+    fn file_path(&self, network: &str) -> PathBuf {
+        self.root.join(network).join(RESOLUTION_CACHE_FILE)
+    }
+}
+
+fn loaded() -> &'static Mutex<HashMap<PathBuf, ResolutionCacheFile>> {
+    LOADED.get_or_init(Default::default)
+}
+
+fn read_file(path: &Path) -> Result<ResolutionCacheFile> {
+    if !path.exists() {
+        return Ok(ResolutionCacheFile::default());
+    }
+
+    let content =
+        fs::read(path).with_context(|| format!("Failed to read cache file: {}", path.display()))?;
+
+    serde_json::from_slice(&content)
+        .with_context(|| format!("Failed to parse cache file: {}", path.display()))
+}
+
+/// The current time, in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,14 @@
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders a list of values as a single-line sparkline (e.g. `▁▃▅█`), scaled to the largest value.
+pub fn sparkline(values: &[i64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+
+    values
+        .iter()
+        .map(|value| {
+            let index = (value.max(&0) * (SPARK_CHARS.len() as i64 - 1) + max / 2) / max;
+            SPARK_CHARS[index as usize]
+        })
+        .collect()
+}
@@ -1,14 +1,19 @@
-use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::Error;
 use anyhow::Result;
 use regex::Regex;
+use semver::{BuildMetadata, Prerelease, Version};
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::process::Output;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
 use sui_sdk_types::Address;
 use yansi::Paint;
 
@@ -18,8 +23,140 @@ use crate::get_chain_id;
 use crate::types::api_types::PackageRequest;
 use crate::types::api_types::SafeGitInfo;
 use crate::types::Network;
+use crate::utils::package_cache::{is_offline_mode, PackageCache};
+use crate::utils::sui_fixtures::{SuiFixture, SuiRun};
 
-const VERSION_REGEX: &str = r"(\d+)\.(\d+)\.(\d+)";
+const VERSION_REGEX: &str = r"(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.-]+))?";
+
+/// The pre-release of the nightly builds of the Sui CLI (e.g. `sui 1.63.0-nightly`).
+const NIGHTLY_PRERELEASE: &str = "nightly";
+
+/// Commands whose output does not change while the CLI runs, so `sui` only runs them once.
+const CACHED_COMMANDS: [&[&str]; 2] = [&["--version"], &["client", "chain-identifier"]];
+
+tokio::task_local! {
+    static SUI_BINARY: Arc<dyn SuiBinary>;
+}
+
+/// Runs the commands of the Sui CLI.
+///
+/// The CLI runs the `sui` binary ([`ProcessSuiBinary`]), unless another implementation is set
+/// for the current task with [`with_sui_binary`] (e.g. a `ScriptedSuiBinary` in tests).
+pub trait SuiBinary: Send + Sync {
+    /// Runs the binary with the given arguments.
+    fn run(&self, args: &[&str]) -> Result<SuiOutput, CliError>;
+
+    /// The binary that is run (e.g. `sui`, or the value of `SUI_BINARY_PATH`).
+    fn binary(&self) -> String;
+}
+
+/// The output of a Sui CLI command.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SuiOutput {
+    /// The exit code of the command (`None` if it was terminated by a signal).
+    pub status: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl SuiOutput {
+    /// The output of a successful command that printed `stdout`.
+    pub fn ok(stdout: impl Into<String>) -> Self {
+        Self {
+            status: Some(0),
+            stdout: stdout.into(),
+            stderr: String::new(),
+        }
+    }
+
+    /// The output of a failed command that printed `stderr`.
+    pub fn error(stderr: impl Into<String>) -> Self {
+        Self {
+            status: Some(1),
+            stdout: String::new(),
+            stderr: stderr.into(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status == Some(0)
+    }
+}
+
+impl From<Output> for SuiOutput {
+    fn from(output: Output) -> Self {
+        Self {
+            status: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }
+    }
+}
+
+/// The `sui` binary (or the one set in `SUI_BINARY_PATH`).
+///
+/// The output of `CACHED_COMMANDS` is cached, and if `MVR_SUI_RECORD_FILE` is set, every run
+/// is recorded to that file, so it can be replayed in tests.
+#[derive(Default)]
+pub struct ProcessSuiBinary {
+    cache: Mutex<HashMap<Vec<String>, SuiOutput>>,
+}
+
+impl SuiBinary for ProcessSuiBinary {
+    fn run(&self, args: &[&str]) -> Result<SuiOutput, CliError> {
+        let key = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let cached = CACHED_COMMANDS.contains(&args);
+
+        if cached {
+            if let Some(output) = self.cache.lock().unwrap().get(&key) {
+                return Ok(output.clone());
+            }
+        }
+
+        let (bin, env) = get_sui_binary();
+        let output: SuiOutput = Command::new(bin)
+            .args(args)
+            .output()
+            .map_err(|_| CliError::SuiBinaryNotFound(env))?
+            .into();
+
+        if let Some(path) = env::var_os(EnvVariables::MvrSuiRecordFile.to_string()) {
+            let run = SuiRun {
+                args: key.clone(),
+                output: output.clone(),
+            };
+
+            if let Err(e) = SuiFixture::record(Path::new(&path), run) {
+                eprintln!("{} {}", "[mvr] Failed to record Sui run:".yellow(), e);
+            }
+        }
+
+        if cached {
+            self.cache.lock().unwrap().insert(key, output.clone());
+        }
+
+        Ok(output)
+    }
+
+    fn binary(&self) -> String {
+        get_sui_binary().0
+    }
+}
+
+/// Runs `f` with `binary` running every Sui CLI command, instead of the `sui` binary.
+pub async fn with_sui_binary<F: Future>(binary: Arc<dyn SuiBinary>, f: F) -> F::Output {
+    SUI_BINARY.scope(binary, f).await
+}
+
+fn sui_binary() -> Arc<dyn SuiBinary> {
+    static PROCESS_SUI_BINARY: OnceLock<Arc<dyn SuiBinary>> = OnceLock::new();
+
+    SUI_BINARY.try_with(Arc::clone).unwrap_or_else(|_| {
+        PROCESS_SUI_BINARY
+            .get_or_init(|| Arc::new(ProcessSuiBinary::default()))
+            .clone()
+    })
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 // The result of `cache-package` command
@@ -32,59 +169,126 @@ pub struct SuiCachePackageResponse {
     pub chain_id: String,
 }
 
-/// Check the sui binary's version and print it to the console.
-/// This can be used
-pub fn check_sui_version(expected_version: (u32, u32)) -> Result<(), Error> {
-    let output = sui_command(["--version"].to_vec())?;
-
-    // Check if the command was successful
-    if output.status.success() {
-        // Convert the stdout (standard output) bytes into a string
-        let version_output = String::from_utf8_lossy(&output.stdout);
-        let re = Regex::new(VERSION_REGEX)
-            .map_err(|_| anyhow!("Failed to get the version of the SUI binary."))?;
-
-        // Search the output for the version number
-        if let Some(caps) = re.captures(&version_output) {
-            let major_str = caps
-                .get(1)
-                .ok_or_else(|| anyhow!("Failed to get the major version of the SUI binary."))?
-                .as_str();
-            let minor_str = caps
-                .get(2)
-                .ok_or_else(|| anyhow!("Failed to get the minor version of the SUI binary."))?
-                .as_str();
-
-            // Extract the major and minor version numbers
-            let major: u32 = major_str.parse().map_err(|_| {
-                anyhow!("Major version {} of SUI Binary is not a number.", major_str)
-            })?;
-
-            let minor: u32 = minor_str.parse().map_err(|_| {
-                anyhow!("Minor version {} of SUI Binary is not a number.", minor_str)
-            })?;
-
-            assert!(
-                major >= expected_version.0 && minor >= expected_version.1,
-                "{}",
-                &format!(
-                    "SUI version is too low. Please upgrade to at least {}.{} in order to build your code using mvr.",
-                    &expected_version.0, &expected_version.1
-                ),
-            );
+/// What we know about the `sui` binary used by the CLI.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SuiBinaryReport {
+    /// The binary, as configured (`sui`, or the value of `SUI_BINARY_PATH`).
+    pub binary: String,
+    /// The full path of the binary, if it could be found.
+    pub path: Option<PathBuf>,
+    /// The version, as reported by `sui --version`.
+    pub version: String,
+    /// The chain identifier of the active environment, if it could be detected.
+    pub chain_id: Option<String>,
+}
+
+/// Check that the sui binary's version is at least `required`, and print it to the console.
+pub fn check_sui_version(required: &Version) -> Result<(), Error> {
+    let (found, version) = sui_version()?;
+
+    if !is_supported_version(&version, required) {
+        bail!(CliError::SuiVersionTooLow {
+            found,
+            required: required.to_string(),
+        });
+    }
+
+    eprintln!("{}", "[mvr] detected supported SUI CLI version".blue());
+    Ok(())
+}
+
+/// Returns the binary, path, version and active chain of the `sui` binary.
+/// The version and chain are only detected once, and cached for the lifetime of the process.
+pub fn sui_binary_report() -> Result<SuiBinaryReport, Error> {
+    let binary = sui_binary().binary();
+    let (version, _) = sui_version()?;
+
+    Ok(SuiBinaryReport {
+        path: find_binary(&binary),
+        binary,
+        version,
+        chain_id: active_chain_id().ok().filter(|id| !id.is_empty()),
+    })
+}
+
+/// Parses the version from the output of `sui --version` (e.g. `sui 1.63.0-0362997459`).
+///
+/// Sui appends the git revision of the build (e.g. `0362997459`), or a pre-release (e.g. `rc.1`,
+/// `nightly`), to its version. Git revisions (hexadecimal suffixes) are kept as build metadata, so
+/// they do not turn a release into a pre-release, and other valid pre-releases are kept as such.
+pub fn parse_sui_version(output: &str) -> Result<Version, CliError> {
+    let re = Regex::new(VERSION_REGEX).expect("the version regex is valid");
+    let invalid = || CliError::InvalidSuiVersion(output.trim().to_string());
+
+    let caps = re.captures(output).ok_or_else(invalid)?;
+    let number = |i: usize| caps[i].parse::<u64>().map_err(|_| invalid());
+
+    let mut version = Version::new(number(1)?, number(2)?, number(3)?);
+
+    if let Some(suffix) = caps.get(4).map(|m| m.as_str()) {
+        let is_revision = suffix.chars().all(|c| c.is_ascii_hexdigit());
 
-            eprintln!("{}", "[mvr] detected supported SUI CLI version".blue());
-        } else {
-            eprintln!("Could not find version components in the output.");
+        match Prerelease::new(suffix) {
+            Ok(pre) if !is_revision => version.pre = pre,
+            _ => version.build = BuildMetadata::new(suffix).map_err(|_| invalid())?,
         }
-    } else {
-        // If the command fails, handle the error
-        eprintln!(
-            "Failed to get version info. Stderr: {}",
-            String::from_utf8_lossy(&output.stderr)
+    }
+
+    Ok(version)
+}
+
+/// Whether a version of the Sui CLI satisfies the `required` (minimum) version, using semver
+/// ordering: pre-releases of the required version (e.g. `1.63.0-rc.1`) do not satisfy it.
+///
+/// The only exception are `nightly` builds of the required version, which are whitelisted.
+pub fn is_supported_version(version: &Version, required: &Version) -> bool {
+    let is_nightly_of_required = version.pre.as_str() == NIGHTLY_PRERELEASE
+        && (version.major, version.minor, version.patch)
+            == (required.major, required.minor, required.patch);
+
+    version >= required || is_nightly_of_required
+}
+
+fn sui_version() -> Result<(String, Version), Error> {
+    let output = sui_command(&["--version"])?;
+
+    if !output.is_success() {
+        bail!(
+            "Failed to get the version of the Sui CLI: {}",
+            output.stderr.trim()
         );
     }
-    Ok(())
+
+    let version = parse_sui_version(&output.stdout)?;
+    let found = raw_version(&output.stdout).unwrap_or_else(|| version.to_string());
+
+    Ok((found, version))
+}
+
+/// The version, exactly as printed by the binary.
+fn raw_version(output: &str) -> Option<String> {
+    Regex::new(VERSION_REGEX)
+        .ok()?
+        .find(output)
+        .map(|m| m.as_str().to_string())
+}
+
+fn active_chain_id() -> Result<String, CliError> {
+    let cli_output = sui_command(&["client", "chain-identifier"])?;
+    Ok(cli_output.stdout.trim().to_string())
+}
+
+/// Finds the full path of a binary, looking it up in `PATH` if it is not a path itself.
+fn find_binary(binary: &str) -> Option<PathBuf> {
+    let path = Path::new(binary);
+
+    if path.components().count() > 1 {
+        return path.canonicalize().ok();
+    }
+
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(binary))
+        .find(|candidate| candidate.is_file() || candidate.with_extension("exe").is_file())
 }
 
 /// Gets the active network by calling `sui client chain-identifier` from the Sui CLI, or falls back
@@ -92,13 +296,9 @@ pub fn check_sui_version(expected_version: (u32, u32)) -> Result<(), Error> {
 ///
 /// Returns the network as a `Network` enum, or errors if network is not `mainnet` or `testnet`.
 pub fn get_active_network() -> Result<Network, Error> {
-    let fallback_network = env::var("MVR_FALLBACK_NETWORK");
+    let fallback_network = env::var(EnvVariables::MvrFallbackNetwork.to_string());
 
-    let cli_output = sui_command(["client", "chain-identifier"].to_vec())?;
-
-    let chain_id = String::from_utf8_lossy(&cli_output.stdout)
-        .trim()
-        .to_string();
+    let chain_id = active_chain_id()?;
 
     let cli_network = Network::try_from_chain_identifier(&chain_id);
 
@@ -117,44 +317,111 @@ pub fn get_active_network() -> Result<Network, Error> {
     Ok(cli_network)
 }
 
+/// Caches a package (and its git source) using `sui move cache-package`.
+///
+/// Successful responses are stored in the local package cache, keyed by the pinned name
+/// of the package (e.g. `@org/app/3`). In offline mode, the response is only looked up in that cache.
 pub fn cache_package(
     dependency: PackageRequest,
     network: &Network,
 ) -> Result<SuiCachePackageResponse, Error> {
+    let pinned_name = format!("{}/{}", dependency.name, dependency.version);
+
+    if is_offline_mode() {
+        let Some(cached) = PackageCache::from_env()?.get_cached_package(network, &pinned_name)?
+        else {
+            bail!(CliError::NotCachedOffline(pinned_name, network.to_string()));
+        };
+
+        return Ok(cached);
+    }
+
     let git_info: SafeGitInfo = dependency.get_git_info()?.try_into()?;
+    let response = cache_git_package(&git_info, network)?;
+
+    if let Err(e) = PackageCache::from_env()
+        .and_then(|cache| cache.put_cached_package(network, &pinned_name, &response))
+    {
+        eprintln!(
+            "{} {}",
+            "[mvr] Failed to update the local cache:".yellow(),
+            e
+        );
+    }
 
+    Ok(response)
+}
+
+/// Caches the package at a git source using `sui move cache-package`, and returns its name and addresses.
+pub fn cache_git_package(
+    git_info: &SafeGitInfo,
+    network: &Network,
+) -> Result<SuiCachePackageResponse, Error> {
     // Make the dependency like this: { git = "...", rev = "...", subdir = "..." }
     let dependency_str = format!(
         "{{ git = \"{}\", rev = \"{}\", subdir = \"{}\" }}",
         git_info.repository_url, git_info.tag, git_info.path
     );
     let network_name = network.to_string();
-    let chain_id = get_chain_id(&network)?;
-
-    let cli_output = sui_command(
-        [
-            "move",
-            "cache-package",
-            &network_name,
-            chain_id.as_str(),
-            dependency_str.as_str(),
-        ]
-        .to_vec(),
-    )?;
-
-    let response_str = String::from_utf8_lossy(&cli_output.stdout).to_string();
-    let response: SuiCachePackageResponse = serde_json::from_str(&response_str)
-        .map_err(|e| CliError::UnexpectedParsing(e.to_string()))?;
+    let chain_id = get_chain_id(network)?;
 
-    Ok(response)
+    let cli_output = sui_command(&[
+        "move",
+        "cache-package",
+        &network_name,
+        chain_id.as_str(),
+        dependency_str.as_str(),
+    ])?;
+
+    Ok(serde_json::from_str(&cli_output.stdout)
+        .map_err(|e| CliError::UnexpectedParsing(e.to_string()))?)
+}
+
+/// Builds the Move package at `path` with `sui move build`, and returns the bytecode of its
+/// modules (without its dependencies), keyed by the module name.
+pub fn build_package_modules(path: &Path) -> Result<BTreeMap<String, Vec<u8>>, Error> {
+    let path_str = path.to_string_lossy();
+    let output = sui_command(&["move", "build", "--path", &path_str])?;
+
+    if !output.is_success() {
+        bail!(
+            "Failed to build the package at {}: {}",
+            path.display(),
+            output.stderr.trim()
+        );
+    }
+
+    let mut modules = BTreeMap::new();
+
+    // Modules are written to `build/<package>/bytecode_modules/<module>.mv`, and the modules of
+    // the dependencies to the `dependencies` directory next to them.
+    for package_dir in fs::read_dir(path.join("build"))? {
+        let bytecode_dir = package_dir?.path().join("bytecode_modules");
+        if !bytecode_dir.is_dir() {
+            continue;
+        }
+
+        for entry in fs::read_dir(bytecode_dir)? {
+            let module_path = entry?.path();
+            if module_path.extension().and_then(|ext| ext.to_str()) != Some("mv") {
+                continue;
+            }
+
+            let Some(module) = module_path.file_stem() else {
+                continue;
+            };
+            modules.insert(
+                module.to_string_lossy().to_string(),
+                fs::read(&module_path)?,
+            );
+        }
+    }
+
+    Ok(modules)
 }
 
-fn sui_command(args: Vec<&str>) -> Result<Output, CliError> {
-    let (bin, env) = get_sui_binary();
-    Command::new(bin)
-        .args(args)
-        .output()
-        .map_err(|_| CliError::SuiBinaryNotFound(env))
+fn sui_command(args: &[&str]) -> Result<SuiOutput, CliError> {
+    sui_binary().run(args)
 }
 
 fn get_sui_binary() -> (String, String) {
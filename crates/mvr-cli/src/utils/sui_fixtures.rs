@@ -0,0 +1,133 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::sui_binary::SuiOutput;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use scripted::ScriptedSuiBinary;
+
+/// A run of the Sui CLI: the arguments it was called with, and its output.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SuiRun {
+    pub args: Vec<String>,
+    #[serde(flatten)]
+    pub output: SuiOutput,
+}
+
+/// A list of Sui CLI runs, stored as a JSON array. Fixtures can be written by hand, or recorded
+/// from real runs by setting `MVR_SUI_RECORD_FILE` to the path of the fixture.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct SuiFixture {
+    pub runs: Vec<SuiRun>,
+}
+
+impl SuiFixture {
+    /// Reads a fixture from disk. A missing file is treated as an empty fixture.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read Sui fixture: {}", path.display()))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse Sui fixture: {}", path.display()))
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write Sui fixture: {}", path.display()))
+    }
+
+    /// Appends a run to the fixture at `path`.
+    pub fn record(path: &Path, run: SuiRun) -> Result<()> {
+        let mut fixture = Self::read_from_file(path)?;
+        fixture.runs.push(run);
+        fixture.write_to_file(path)
+    }
+}
+
+/// Test tooling to replay fixtures, only built with the `test-utils` feature.
+#[cfg(any(test, feature = "test-utils"))]
+mod scripted {
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use anyhow::Result;
+
+    use super::{SuiFixture, SuiRun};
+    use crate::errors::CliError;
+    use crate::utils::sui_binary::{SuiBinary, SuiOutput};
+
+    /// A fake Sui binary, which answers commands with scripted (or recorded) outputs.
+    ///
+    /// A command is answered by the run whose arguments are the longest prefix of the command's
+    /// arguments (the last one added, if several match), so a run for `["move", "cache-package"]`
+    /// answers every `cache-package` command. Commands without a matching run fail.
+    #[derive(Debug, Default)]
+    pub struct ScriptedSuiBinary {
+        runs: Vec<SuiRun>,
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl ScriptedSuiBinary {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Replays the runs of a fixture.
+        pub fn from_fixture(path: &Path) -> Result<Self> {
+            Ok(Self {
+                runs: SuiFixture::read_from_file(path)?.runs,
+                ..Self::default()
+            })
+        }
+
+        /// Answers the commands starting with `args` with `output`.
+        pub fn on(mut self, args: &[&str], output: SuiOutput) -> Self {
+            self.runs.push(SuiRun {
+                args: args.iter().map(|arg| arg.to_string()).collect(),
+                output,
+            });
+            self
+        }
+
+        /// The arguments of every command run so far, in order.
+        pub fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl SuiBinary for ScriptedSuiBinary {
+        fn run(&self, args: &[&str]) -> Result<SuiOutput, CliError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(args.iter().map(|arg| arg.to_string()).collect());
+
+            let output = self
+                .runs
+                .iter()
+                .filter(|run| {
+                    run.args.len() <= args.len()
+                        && run.args.iter().zip(args).all(|(a, b)| a.as_str() == *b)
+                })
+                .max_by_key(|run| run.args.len())
+                .map(|run| run.output.clone())
+                .unwrap_or_else(|| {
+                    SuiOutput::error(format!("No scripted output for `sui {}`", args.join(" ")))
+                });
+
+            Ok(output)
+        }
+
+        fn binary(&self) -> String {
+            "scripted".to_string()
+        }
+    }
+}
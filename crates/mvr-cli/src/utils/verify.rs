@@ -0,0 +1,100 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use sha2::{Digest, Sha256};
+use sui_sdk_types::Address;
+
+use crate::types::api_types::SafeGitInfo;
+use crate::types::{ModuleStatus, ModuleVerification};
+
+/// Checks out the source of a package (only the recorded `rev`, without history) into `dir`,
+/// and returns the path of the package inside the repository.
+pub fn checkout_git_source(git: &SafeGitInfo, dir: &Path) -> Result<PathBuf> {
+    let commands: [&[&str]; 4] = [
+        &["init", "--quiet"],
+        &["remote", "add", "origin", &git.repository_url],
+        &["fetch", "--quiet", "--depth", "1", "origin", &git.tag],
+        &["checkout", "--quiet", "FETCH_HEAD"],
+    ];
+
+    for args in commands {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .context("Failed to run `git`. Make sure it is installed and on your PATH.")?;
+
+        if !output.status.success() {
+            bail!(
+                "`git {}` failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    Ok(dir.join(&git.path))
+}
+
+/// Compares the modules built from source against the on-chain modules of a package.
+///
+/// A package built from source refers to itself as `0x0`, while its on-chain modules refer to
+/// the original ID of the package, so the self address of every source module is substituted
+/// before comparing the bytecode.
+pub fn compare_modules(
+    source: &BTreeMap<String, Vec<u8>>,
+    on_chain: &BTreeMap<String, Vec<u8>>,
+    original_id: &Address,
+) -> Result<Vec<ModuleVerification>> {
+    let modules: BTreeSet<&String> = source.keys().chain(on_chain.keys()).collect();
+
+    modules
+        .into_iter()
+        .map(|module| {
+            let source_bytes = source
+                .get(module)
+                .map(|bytes| with_self_address(bytes, original_id))
+                .transpose()
+                .with_context(|| format!("Failed to read the bytecode of module `{module}`"))?;
+            let on_chain_bytes = on_chain.get(module);
+
+            let status = match (&source_bytes, on_chain_bytes) {
+                (Some(source), Some(on_chain)) if source == on_chain => ModuleStatus::Match,
+                (Some(_), Some(_)) => ModuleStatus::Mismatch,
+                (Some(_), None) => ModuleStatus::MissingOnChain,
+                (None, _) => ModuleStatus::MissingFromSource,
+            };
+
+            Ok(ModuleVerification {
+                module: module.clone(),
+                status,
+                source_digest: source_bytes.as_deref().map(module_digest),
+                on_chain_digest: on_chain_bytes.map(|bytes| module_digest(bytes)),
+            })
+        })
+        .collect()
+}
+
+/// Returns the (hex encoded) sha256 digest of a module's bytecode.
+pub fn module_digest(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Sets the address of the module's own handle (and so of every module of the same package)
+/// to `address`, leaving any other data (e.g. address constants) untouched.
+fn with_self_address(bytes: &[u8], address: &Address) -> Result<Vec<u8>> {
+    let mut module = CompiledModule::deserialize_with_defaults(bytes)
+        .map_err(|e| anyhow!("Invalid module bytecode: {:?}", e))?;
+
+    let self_address = module.self_handle().address.0 as usize;
+    module.address_identifiers[self_address] = AccountAddress::new(*address.as_bytes());
+
+    let mut result = vec![];
+    module.serialize_with_version(module.version, &mut result)?;
+
+    Ok(result)
+}
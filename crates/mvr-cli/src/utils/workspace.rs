@@ -0,0 +1,117 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{bail, Context, Result};
+use mvr_types::name::VersionedName;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::manifest::MoveToml;
+
+const MOVE_TOML: &str = "Move.toml";
+
+/// Directories that never contain workspace packages (build outputs, VCS metadata, etc).
+const SKIPPED_DIRS: [&str; 3] = ["build", "node_modules", "target"];
+
+/// A set of Move packages, discovered under a common root directory.
+pub struct Workspace {
+    pub root: PathBuf,
+    pub packages: Vec<MoveToml>,
+}
+
+/// An MVR name that is requested with different versions across the packages of a workspace.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PinConflict {
+    /// The unversioned MVR name (e.g. `@org/app`).
+    pub name: String,
+    /// Every `Move.toml` that depends on the name, with the name as it is requested there.
+    pub pins: Vec<(PathBuf, String)>,
+}
+
+impl Workspace {
+    /// Discovers every `Move.toml` beneath `root` (skipping hidden and build directories).
+    pub fn discover(root: &Path) -> Result<Self> {
+        let mut paths = vec![];
+        find_move_tomls(root, &mut paths)?;
+        paths.sort();
+
+        if paths.is_empty() {
+            bail!("Could not find any Move.toml files in {}", root.display());
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            packages: paths
+                .into_iter()
+                .map(MoveToml::new)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Returns the union of MVR names requested by all packages of the workspace. Names with a
+    /// network override are left out, as they are not resolved on the network of the build.
+    pub fn mvr_names(&self) -> BTreeSet<String> {
+        self.packages
+            .iter()
+            .flat_map(|package| package.get_mvr_dependencies())
+            .filter(|dependency| dependency.network.is_none())
+            .map(|dependency| dependency.name)
+            .collect()
+    }
+
+    /// Returns the MVR names that are requested with more than one version across the workspace.
+    /// An unversioned name (which resolves to the latest version) conflicts with a pinned one.
+    pub fn pin_conflicts(&self) -> Vec<PinConflict> {
+        let mut pins: BTreeMap<String, Vec<(PathBuf, String)>> = BTreeMap::new();
+
+        for package in &self.packages {
+            let path = package.path.clone().unwrap_or_default();
+
+            for dependency in package.get_mvr_dependencies() {
+                let Ok(name) = VersionedName::from_str(&dependency.name) else {
+                    continue;
+                };
+
+                pins.entry(name.name.to_string())
+                    .or_default()
+                    .push((path.clone(), dependency.name));
+            }
+        }
+
+        pins.into_iter()
+            .filter(|(_, pins)| {
+                pins.iter()
+                    .map(|(_, name)| name)
+                    .collect::<BTreeSet<_>>()
+                    .len()
+                    > 1
+            })
+            .map(|(name, pins)| PinConflict { name, pins })
+            .collect()
+    }
+}
+
+fn find_move_tomls(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read directory {}", dir.display()))?;
+
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        // Symlinks are not followed (`file_type` does not traverse them), so they cannot loop.
+        if entry.file_type()?.is_dir() {
+            if !file_name.starts_with('.') && !SKIPPED_DIRS.contains(&file_name.as_ref()) {
+                find_move_tomls(&path, paths)?;
+            }
+        } else if file_name == MOVE_TOML {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, Once,
+    },
+    thread,
+};
+
+use mvr::{
+    errors::CliError,
+    get_pinned_dependencies,
+    types::{api_data::query_each_dependency, api_types::PackageRequest, Network},
+    utils::manifest::MoveToml,
+};
+use mvr_types::api::{BulkNamesRequest, BulkNamesResponse};
+
+static SETUP: Once = Once::new();
+
+/// The names of every `names/bulk` request received by the local MVR API.
+static BULK_REQUESTS: Mutex<Vec<Vec<String>>> = Mutex::new(vec![]);
+
+/// The latest version of `@mvr/latest`, which tests can bump.
+static LATEST_VERSION: AtomicU64 = AtomicU64::new(1);
+
+/// Points the CLI to a local MVR API supporting the bulk endpoint (which knows `@mvr/demo` and
+/// `@mvr/latest`), and to an empty cache and config.
+fn setup() {
+    SETUP.call_once(|| {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for mut socket in listener.incoming().flatten() {
+                let (head, body) = read_request(&mut socket);
+
+                let response = if head.starts_with("POST /v1/names/bulk ") {
+                    let request: BulkNamesRequest = serde_json::from_slice(&body).unwrap();
+                    BULK_REQUESTS.lock().unwrap().push(request.names.clone());
+
+                    let names = request
+                        .names
+                        .into_iter()
+                        .map(|name| {
+                            let version = match name.as_str() {
+                                "@mvr/demo" => Some(1),
+                                "@mvr/latest" => Some(LATEST_VERSION.load(Ordering::SeqCst)),
+                                _ => None,
+                            };
+                            let package = version.map(|version| {
+                                serde_json::from_value(serde_json::json!({
+                                    "name": name,
+                                    "metadata": {},
+                                    "package_info": null,
+                                    "git_info": null,
+                                    "version": version,
+                                    "package_address": format!("0x{version}")
+                                }))
+                                .unwrap()
+                            });
+                            (name, package)
+                        })
+                        .collect();
+
+                    let body = serde_json::to_string(&BulkNamesResponse { names }).unwrap();
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                };
+
+                let _ = socket.write_all(response.as_bytes());
+            }
+        });
+
+        let dir = tempfile::TempDir::new().unwrap().keep();
+        std::env::set_var("MVR_CACHE_DIR", dir.join("cache"));
+        std::env::set_var("MVR_CONFIG", dir.join("config.toml"));
+        std::env::set_var("MVR_NETWORK_MAINNET_API_URL", &url);
+    });
+}
+
+/// Reads the head and (`Content-Length` long) body of a request.
+fn read_request(socket: &mut TcpStream) -> (String, Vec<u8>) {
+    let mut request = vec![];
+    let mut buf = [0; 8192];
+
+    loop {
+        let n = socket.read(&mut buf).unwrap_or_default();
+        request.extend_from_slice(&buf[..n]);
+
+        let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+            if n == 0 {
+                return (String::from_utf8_lossy(&request).to_string(), vec![]);
+            }
+            continue;
+        };
+
+        let head = String::from_utf8_lossy(&request[..end]).to_string();
+        let length = head
+            .lines()
+            .find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                key.eq_ignore_ascii_case("content-length")
+                    .then(|| value.trim().parse::<usize>().ok())?
+            })
+            .unwrap_or(0);
+
+        if n == 0 || request.len() >= end + 4 + length {
+            return (head, request[end + 4..].to_vec());
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_query_with_malformed_name() {
+    setup();
+
+    let names = ["@mvr/demo", "not a valid name", "@mvr/missing"].map(String::from);
+    let results = query_each_dependency(&names, &Network::Mainnet, false)
+        .await
+        .unwrap();
+
+    // A malformed name only fails its own result.
+    assert_eq!(results[0].as_ref().unwrap().name, "@mvr/demo");
+    assert!(results[1]
+        .as_ref()
+        .unwrap_err()
+        .to_string()
+        .contains("not a valid name"));
+    assert_eq!(
+        results[2].as_ref().unwrap_err().downcast_ref::<CliError>(),
+        Some(&CliError::NameNotExists(
+            "@mvr/missing".to_string(),
+            "mainnet".to_string()
+        ))
+    );
+
+    // ... and is not sent to the API.
+    let requests = BULK_REQUESTS.lock().unwrap();
+    assert!(requests.contains(&vec!["@mvr/demo".to_string(), "@mvr/missing".to_string()]));
+    assert!(!requests
+        .iter()
+        .flatten()
+        .any(|name| name == "not a valid name"));
+}
+
+#[tokio::test]
+async fn test_latest_version_lookups_bypass_the_cache() {
+    setup();
+    let names = ["@mvr/latest".to_string()];
+    let version = |results: Vec<anyhow::Result<PackageRequest>>| {
+        results
+            .into_iter()
+            .map(|result| result.unwrap().version)
+            .collect::<Vec<u64>>()
+    };
+
+    let results = query_each_dependency(&names, &Network::Mainnet, false)
+        .await
+        .unwrap();
+    assert_eq!(version(results), [1]);
+
+    // A new version is published: regular lookups are still answered from the cache ...
+    LATEST_VERSION.store(2, Ordering::SeqCst);
+    let results = query_each_dependency(&names, &Network::Mainnet, false)
+        .await
+        .unwrap();
+    assert_eq!(version(results), [1]);
+
+    // ... but refreshing ones (like `mvr outdated` and `mvr update`) see the new version.
+    let results = query_each_dependency(&names, &Network::Mainnet, true)
+        .await
+        .unwrap();
+    assert_eq!(version(results), [2]);
+
+    LATEST_VERSION.store(3, Ordering::SeqCst);
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("Move.toml");
+    std::fs::write(
+        &path,
+        "[package]\nname = \"app\"\n\n[dependencies]\nlatest = { r.mvr = \"@mvr/latest/1\" }\n",
+    )
+    .unwrap();
+
+    let pinned = get_pinned_dependencies(&MoveToml::new(path).unwrap(), None, &Network::Mainnet)
+        .await
+        .unwrap();
+    assert_eq!(pinned[0].latest_version, 3);
+}
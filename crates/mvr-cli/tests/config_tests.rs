@@ -0,0 +1,100 @@
+use std::str::FromStr;
+
+use mvr::{
+    errors::CliError,
+    types::{api_data::get_api_url, Network},
+    utils::config::{MvrConfig, NetworkConfig},
+};
+use tempfile::TempDir;
+
+#[test]
+fn test_config_with_env_overrides() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("config.toml");
+
+    // A missing config file reads as an empty config.
+    assert_eq!(
+        MvrConfig::read_from_file(&path).unwrap(),
+        MvrConfig::default()
+    );
+
+    std::fs::write(
+        &path,
+        r#"
+[networks.devnet]
+chain_id = "aba3e445"
+api_url = "https://devnet.mvr.example.com"
+
+[networks.mainnet]
+api_url = "https://mvr.example.com"
+"#,
+    )
+    .unwrap();
+
+    let mut config = MvrConfig::read_from_file(&path).unwrap();
+
+    config.apply_env_overrides([
+        (
+            "MVR_NETWORK_DEVNET_API_URL".to_string(),
+            "http://localhost:8000".to_string(),
+        ),
+        (
+            "MVR_NETWORK_LOCALNET_CHAIN_ID".to_string(),
+            "4b3c2a1f".to_string(),
+        ),
+        ("MVR_OFFLINE".to_string(), "1".to_string()),
+    ]);
+
+    assert_eq!(
+        config.network("devnet"),
+        Some(&NetworkConfig {
+            chain_id: Some("aba3e445".to_string()),
+            api_url: Some("http://localhost:8000".to_string()),
+        })
+    );
+    assert_eq!(
+        config.network("mainnet"),
+        Some(&NetworkConfig {
+            chain_id: None,
+            api_url: Some("https://mvr.example.com".to_string()),
+        })
+    );
+    assert_eq!(config.network_by_chain_id("4b3c2a1f"), Some("localnet"));
+    assert_eq!(config.network_by_chain_id("35834a8a"), None);
+    assert_eq!(config.networks.len(), 3);
+}
+
+#[test]
+fn test_malformed_config_only_fails_custom_networks() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "[networks.devnet\n").unwrap();
+    std::env::set_var("MVR_CONFIG", &path);
+
+    // Mainnet and testnet do not need the config file.
+    assert_eq!(Network::from_str("mainnet").unwrap(), Network::Mainnet);
+    assert_eq!(
+        Network::try_from_chain_identifier("4c78adac").unwrap(),
+        Network::Testnet
+    );
+    assert_eq!(
+        get_api_url(&Network::Mainnet).unwrap(),
+        "https://mainnet.mvr.mystenlabs.com"
+    );
+
+    // Custom networks (and unknown chain identifiers) do.
+    for err in [
+        Network::from_str("devnet").unwrap_err(),
+        Network::try_from_chain_identifier("aba3e445").unwrap_err(),
+    ] {
+        let CliError::InvalidConfig(message) = err else {
+            panic!("Expected an invalid config error, got {err:?}");
+        };
+        assert!(message.contains(&path.display().to_string()));
+    }
+
+    // Messages point to the config file in use.
+    assert!(CliError::InvalidNetwork("devnet".to_string())
+        .to_string()
+        .contains(&path.display().to_string()));
+}
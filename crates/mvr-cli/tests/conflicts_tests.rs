@@ -0,0 +1,162 @@
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+use mvr::{
+    types::{CheckedDependency, ConflictKind, Network},
+    utils::{
+        conflicts::{check_dependencies, find_conflicts},
+        manifest::MoveToml,
+    },
+};
+use sui_sdk_types::Address;
+use tempfile::TempDir;
+
+fn address(address: &str) -> Option<Address> {
+    Some(Address::from_str(address).unwrap())
+}
+
+fn checked(alias: &str, original_id: &str, published_at: &str) -> CheckedDependency {
+    CheckedDependency {
+        alias: alias.to_string(),
+        source: format!("@mvr/{}", alias),
+        original_id: address(original_id),
+        published_at: address(published_at),
+        error: None,
+    }
+}
+
+fn write_file(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_find_conflicts() {
+    let dependencies = vec![
+        checked("demo", "0x1", "0x1"),
+        checked("demo_again", "0x1", "0x1"),
+        checked("token", "0x2", "0x3"),
+        checked("old_token", "0x2", "0x2"),
+        checked("other", "0x4", "0x4"),
+        CheckedDependency {
+            alias: "broken".to_string(),
+            source: "@mvr/broken".to_string(),
+            original_id: None,
+            published_at: None,
+            error: Some("Failed to cache package".to_string()),
+        },
+    ];
+
+    let addresses = BTreeMap::from([
+        ("app".to_string(), "0x0".to_string()),
+        ("other".to_string(), "0x4".to_string()),
+    ]);
+
+    let conflicts = find_conflicts(&dependencies, &addresses);
+
+    let kinds = conflicts
+        .iter()
+        .map(|conflict| (conflict.kind, conflict.dependencies.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        kinds,
+        vec![
+            (
+                ConflictKind::Duplicate,
+                vec!["demo".to_string(), "demo_again".to_string()]
+            ),
+            (
+                ConflictKind::VersionSkew,
+                vec!["token".to_string(), "old_token".to_string()]
+            ),
+            (
+                ConflictKind::NamedAddress,
+                vec!["other".to_string(), "other".to_string()]
+            ),
+        ]
+    );
+    assert!(conflicts[1]
+        .message
+        .contains(&format!("token (published at {})", address("0x3").unwrap())));
+}
+
+#[tokio::test]
+async fn test_check_local_dependencies() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+
+    write_file(
+        &root.join("app/Move.toml"),
+        r#"[package]
+name = "app"
+
+[dependencies]
+Sui = { git = "https://github.com/MystenLabs/sui.git", subdir = "crates/sui-framework/packages/sui-framework", rev = "framework/testnet" }
+published = { local = "../published" }
+locked = { local = "../locked" }
+manifest = { local = "../manifest" }
+unpublished = { local = "../unpublished" }
+"#,
+    );
+
+    write_file(
+        &root.join("published/Move.toml"),
+        "[package]\nname = \"published\"\n",
+    );
+    write_file(
+        &root.join("published/Published.toml"),
+        "[published.testnet]\noriginal-id = \"0x1\"\npublished-at = \"0x2\"\n",
+    );
+
+    write_file(
+        &root.join("locked/Move.toml"),
+        "[package]\nname = \"locked\"\n",
+    );
+    write_file(
+        &root.join("locked/Move.lock"),
+        "[env.testnet]\noriginal-published-id = \"0x1\"\nlatest-published-id = \"0x1\"\n",
+    );
+
+    write_file(
+        &root.join("manifest/Move.toml"),
+        "[package]\nname = \"manifest\"\npublished-at = \"0x5\"\n\n[addresses]\nmanifest = \"0x5\"\n",
+    );
+
+    write_file(
+        &root.join("unpublished/Move.toml"),
+        "[package]\nname = \"unpublished\"\n\n[addresses]\nunpublished = \"0x0\"\n",
+    );
+
+    let move_toml = MoveToml::new(root.join("app/Move.toml")).unwrap();
+    let dependencies = check_dependencies(&move_toml, &Network::Testnet).await;
+
+    let addresses = dependencies
+        .iter()
+        .map(|dependency| {
+            (
+                dependency.alias.as_str(),
+                dependency.original_id,
+                dependency.published_at,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        addresses,
+        vec![
+            ("published", address("0x1"), address("0x2")),
+            ("locked", address("0x1"), address("0x1")),
+            ("manifest", address("0x5"), address("0x5")),
+            ("unpublished", None, None),
+        ]
+    );
+    assert!(dependencies[3]
+        .error
+        .as_ref()
+        .unwrap()
+        .contains("is not published"));
+
+    let conflicts = find_conflicts(&dependencies, &move_toml.get_addresses());
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::VersionSkew);
+}
@@ -0,0 +1,42 @@
+use insta::assert_snapshot;
+use mvr::{
+    commands::CommandOutput,
+    types::{CheckStatus, DoctorCheck, DoctorReport},
+};
+
+fn check(name: &str, status: CheckStatus, fix: Option<&str>) -> DoctorCheck {
+    DoctorCheck {
+        name: name.to_string(),
+        status,
+        message: format!("{} message", name),
+        fix: fix.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_doctor_report_display() {
+    yansi::disable();
+
+    let mut report = DoctorReport {
+        sui_binary: None,
+        checks: vec![
+            check("Sui binary", CheckStatus::Pass, None),
+            check("Network", CheckStatus::Warn, Some("Switch networks.")),
+        ],
+    };
+
+    assert!(!report.has_failures());
+    assert!(!CommandOutput::Doctor(report.clone()).is_failure());
+
+    report.checks.push(check(
+        "MVR API",
+        CheckStatus::Fail,
+        Some("Check your connection."),
+    ));
+
+    assert!(report.has_failures());
+
+    let output = CommandOutput::Doctor(report);
+    assert!(output.is_failure());
+    assert_snapshot!(output.to_string());
+}
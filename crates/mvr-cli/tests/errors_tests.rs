@@ -0,0 +1,58 @@
+use anyhow::anyhow;
+use mvr::errors::{CliError, ErrorEnvelope};
+use mvr_types::errors::MoveRegistryError;
+use serde_json::json;
+
+fn envelope(error: anyhow::Error) -> serde_json::Value {
+    serde_json::to_value(ErrorEnvelope::from(&error)).unwrap()
+}
+
+#[test]
+fn test_cli_error_envelope() {
+    let error = anyhow!(CliError::NameNotExists(
+        "@mvr/demo".to_string(),
+        "mainnet".to_string()
+    ));
+
+    assert_eq!(
+        envelope(error),
+        json!({
+            "error": {
+                "code": "name_not_found",
+                "message": "The requested package @mvr/demo, on network mainnet, either does not exist, or it does not have an on-chain metadata mapping defined.",
+                "context": {
+                    "name": "@mvr/demo",
+                    "network": "mainnet",
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_wrapped_error_envelope() {
+    // The code is looked up through the whole chain of errors, while the message keeps the context.
+    let error = anyhow!(MoveRegistryError::InvalidType("0x2::coin".to_string()))
+        .context("Failed to resolve types");
+    let envelope = envelope(error);
+
+    assert_eq!(envelope["error"]["code"], "invalid_type");
+    assert_eq!(envelope["error"]["context"]["type"], "0x2::coin");
+    assert!(envelope["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Failed to resolve types: "));
+}
+
+#[test]
+fn test_unknown_error_envelope() {
+    assert_eq!(
+        envelope(anyhow!("Something went wrong")),
+        json!({
+            "error": {
+                "code": "unknown",
+                "message": "Something went wrong",
+            }
+        })
+    );
+}
@@ -0,0 +1,54 @@
+use std::sync::{Arc, Mutex};
+
+use mvr::types::{api_data::api_client, Network};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// Starts a server that answers each connection with the next of `responses`,
+/// and records the (raw) requests it received.
+async fn serve(responses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(vec![]));
+    let received = requests.clone();
+
+    tokio::spawn(async move {
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            received
+                .lock()
+                .unwrap()
+                .push(String::from_utf8_lossy(&buf[..n]).to_string());
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+const UNAVAILABLE: &str =
+    "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok";
+
+#[tokio::test]
+async fn test_api_client_uses_shared_client() {
+    let (url, requests) = serve(vec![UNAVAILABLE, OK]).await;
+    std::env::set_var("MVR_NETWORK_MAINNET_API_URL", &url);
+
+    // Requests of the API client are retried, and carry the `Mvr-Source` header of the CLI.
+    api_client(&Network::Mainnet)
+        .unwrap()
+        .health()
+        .await
+        .unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|r| r.to_lowercase().contains("mvr-source: mvr-cli/")));
+}
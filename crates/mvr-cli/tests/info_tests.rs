@@ -0,0 +1,69 @@
+use mvr::{
+    commands::CommandOutput,
+    types::{
+        api_types::{AnalyticsValue, GitInfo, PackageDependent, PackageRequest},
+        NameInfo, Network,
+    },
+    utils::sparkline::sparkline,
+};
+
+fn package_request(version: u64, git_info: Option<GitInfo>) -> PackageRequest {
+    PackageRequest {
+        name: "@mvr/demo".to_string(),
+        metadata: serde_json::json!({
+            "description": "A demo package",
+            "homepage_url": "https://moveregistry.com",
+        }),
+        package_info: None,
+        git_info,
+        version,
+        package_address: format!("0x{version}"),
+    }
+}
+
+fn analytics(day: u32, total: i64) -> AnalyticsValue {
+    AnalyticsValue {
+        date_from: format!("2025-01-{:02}", day).parse().unwrap(),
+        date_to: format!("2025-01-{:02}", day + 1).parse().unwrap(),
+        direct: total,
+        propagated: 0,
+        total,
+    }
+}
+
+#[test]
+fn test_sparkline() {
+    assert_eq!(sparkline(&[]), "");
+    assert_eq!(sparkline(&[0, 0]), "▁▁");
+    assert_eq!(sparkline(&[0, 1, 2, 3, 4, 5, 6, 7]), "▁▂▃▄▅▆▇█");
+    assert_eq!(sparkline(&[10, 100]), "▂█");
+}
+
+#[test]
+fn test_info_output() {
+    yansi::disable();
+
+    let git_info = GitInfo {
+        repository_url: Some("https://github.com/MystenLabs/mvr".to_string()),
+        path: Some("packages/demo".to_string()),
+        tag: Some("v2".to_string()),
+    };
+
+    let output = CommandOutput::Info(NameInfo {
+        network: Network::Mainnet,
+        package: package_request(3, Some(git_info.clone())),
+        versions: vec![package_request(1, None), package_request(3, Some(git_info))],
+        missing_versions: vec![2],
+        dependencies: vec!["0x1".to_string(), "0x2".to_string()],
+        dependents: vec![PackageDependent {
+            package_id: "0xabc".to_string(),
+            aggregated_total_calls: 42,
+            aggregated_direct_calls: 40,
+            aggregated_propagated_calls: 2,
+        }],
+        total_dependents: 1,
+        analytics: vec![analytics(1, 1), analytics(2, 5), analytics(3, 10)],
+    });
+
+    insta::assert_snapshot!("info_output", output.to_string());
+}
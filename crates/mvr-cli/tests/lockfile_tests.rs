@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+use mvr::{
+    types::{api_types::SafeGitInfo, Network},
+    utils::lockfile::{LockedPackage, MvrLock},
+};
+use sui_sdk_types::Address;
+use tempfile::TempDir;
+
+fn locked_package(version: u64) -> LockedPackage {
+    LockedPackage {
+        version,
+        package_address: format!("0x{version}"),
+        original_id: Address::from_str("0x1").unwrap(),
+        git: SafeGitInfo {
+            repository_url: "https://github.com/MystenLabs/mvr".to_string(),
+            path: "packages/tests/demo".to_string(),
+            tag: "main".to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_lockfile_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let path = MvrLock::path_for(dir.path());
+
+    // A missing lock file reads as an empty lock.
+    let mut lock = MvrLock::read_from_file(&path).unwrap();
+    assert_eq!(lock, MvrLock::default());
+
+    assert!(lock.insert(&Network::Mainnet, "@mvr/demo", locked_package(2)));
+    assert!(lock.insert(&Network::Testnet, "@mvr/demo/1", locked_package(1)));
+    // Re-inserting the same resolution is not a change.
+    assert!(!lock.insert(&Network::Mainnet, "@mvr/demo", locked_package(2)));
+
+    lock.write_to_file(&path).unwrap();
+
+    insta::assert_snapshot!(
+        "mvr_lock",
+        std::fs::read_to_string(&path).unwrap().replace(
+            &Address::from_str("0x1").unwrap().to_string(),
+            "<original_id>"
+        )
+    );
+
+    let read = MvrLock::read_from_file(&path).unwrap();
+    assert_eq!(read, lock);
+    assert_eq!(
+        read.get(&Network::Mainnet, "@mvr/demo"),
+        Some(&locked_package(2))
+    );
+    assert!(read.get(&Network::Testnet, "@mvr/demo").is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_lockfile_rewrite_is_atomic() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let path = MvrLock::path_for(dir.path());
+
+    let mut lock = MvrLock::default();
+    lock.insert(&Network::Mainnet, "@mvr/demo", locked_package(1));
+    lock.write_to_file(&path).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+    lock.insert(&Network::Mainnet, "@mvr/demo", locked_package(2));
+    lock.write_to_file(&path).unwrap();
+
+    // The lock is replaced (keeping its permissions), without leaving temporary files behind.
+    assert_eq!(MvrLock::read_from_file(&path).unwrap(), lock);
+    assert_eq!(
+        std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+        0o600
+    );
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
@@ -1,7 +1,20 @@
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
 
 use insta::assert_snapshot;
-use mvr::utils::manifest::MoveToml;
+use mvr::{
+    errors::CliError,
+    types::{MvrDependency, WriteOptions},
+    utils::manifest::{backup_path, MoveToml},
+};
+use tempfile::TempDir;
+
+fn dependency(alias: &str, name: &str) -> MvrDependency {
+    MvrDependency {
+        alias: alias.to_string(),
+        name: name.to_string(),
+        network: None,
+    }
+}
 
 #[test]
 fn test_regular_manifest() {
@@ -10,9 +23,11 @@ fn test_regular_manifest() {
 
     let mut move_toml = MoveToml::new(path.join("regular.toml")).unwrap();
 
-    move_toml.add_dependency("mvr_demo", "@mvr/demo").unwrap();
     move_toml
-        .add_dependency("another_demo", "@mvr/another_demo")
+        .add_dependency(&dependency("mvr_demo", "@mvr/demo"), false)
+        .unwrap();
+    move_toml
+        .add_dependency(&dependency("another_demo", "@mvr/another_demo"), false)
         .unwrap();
 
     assert_snapshot!(move_toml.doc.to_string());
@@ -25,7 +40,174 @@ fn test_manifest_with_no_dependencies() {
 
     let mut move_toml = MoveToml::new(path.join("missing_deps.toml")).unwrap();
 
-    move_toml.add_dependency("mvr_demo", "@mvr/demo").unwrap();
+    move_toml
+        .add_dependency(&dependency("mvr_demo", "@mvr/demo"), false)
+        .unwrap();
+
+    assert_snapshot!(move_toml.doc.to_string());
+}
+
+#[test]
+fn test_remove_dependency() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "templates"]);
+
+    let mut move_toml = MoveToml::new(path.join("mvr_deps.toml")).unwrap();
+
+    // Remove by alias.
+    let removed = move_toml.remove_dependency("mvr_demo").unwrap().unwrap();
+    assert_eq!(removed.name, "@mvr/demo");
+
+    // Remove by an unversioned name, matching a pinned dependency.
+    let removed = move_toml
+        .remove_dependency("@mvr/another-demo")
+        .unwrap()
+        .unwrap();
+    assert_eq!(removed.alias, "another_demo");
+
+    // Non-MVR dependencies and unknown names are never removed.
+    assert!(move_toml.remove_dependency("Sui").unwrap().is_none());
+    assert!(move_toml
+        .remove_dependency("@mvr/unknown")
+        .unwrap()
+        .is_none());
 
     assert_snapshot!(move_toml.doc.to_string());
 }
+
+#[test]
+fn test_remove_ambiguous_dependency() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("Move.toml");
+    fs::write(
+        &path,
+        "[package]\nname = \"app\"\n\n[dependencies]\napp_v1 = { r.mvr = \"@org/app/1\" }\napp_v2 = { r.mvr = \"@org/app/2\" }\n",
+    )
+    .unwrap();
+
+    let mut move_toml = MoveToml::new(path).unwrap();
+
+    // An unversioned name matching several dependencies does not remove any of them.
+    let err = move_toml.remove_dependency("@org/app").unwrap_err();
+    assert_eq!(
+        err.downcast::<CliError>().unwrap(),
+        CliError::AmbiguousDependency(
+            "@org/app".to_string(),
+            "app_v1 (@org/app/1), app_v2 (@org/app/2)".to_string()
+        )
+    );
+    assert_eq!(move_toml.get_mvr_dependencies().len(), 2);
+
+    // Aliases (and pinned names) only match a single dependency.
+    let removed = move_toml.remove_dependency("app_v2").unwrap().unwrap();
+    assert_eq!(removed.name, "@org/app/2");
+
+    let removed = move_toml.remove_dependency("@org/app").unwrap().unwrap();
+    assert_eq!(removed.alias, "app_v1");
+}
+
+#[test]
+fn test_get_mvr_dependencies() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "templates"]);
+
+    let move_toml = MoveToml::new(path.join("mvr_deps.toml")).unwrap();
+
+    let dependencies = move_toml
+        .get_mvr_dependencies()
+        .into_iter()
+        .map(|dep| (dep.alias, dep.name))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        dependencies,
+        vec![
+            ("mvr_demo".to_string(), "@mvr/demo".to_string()),
+            (
+                "another_demo".to_string(),
+                "@mvr/another-demo/2".to_string()
+            ),
+            ("core".to_string(), "@mvr/core".to_string()),
+        ]
+    );
+
+    let move_toml = MoveToml::new(path.join("missing_deps.toml")).unwrap();
+    assert!(move_toml.get_mvr_dependencies().is_empty());
+}
+
+#[test]
+fn test_add_dependency_conflicts() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "templates"]);
+
+    let mut move_toml = MoveToml::new(path.join("mvr_deps.toml")).unwrap();
+
+    // Re-adding the same dependency is not a conflict.
+    move_toml
+        .add_dependency(&dependency("mvr_demo", "@mvr/demo"), false)
+        .unwrap();
+
+    // A different dependency under an existing key (MVR or not) needs `force`.
+    for alias in ["mvr_demo", "Sui"] {
+        let err = move_toml
+            .add_dependency(&dependency(alias, "@mvr/demo/4"), false)
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CliError>(),
+            Some(CliError::DependencyAlreadyExists(key, _)) if key == alias
+        ));
+    }
+
+    let testnet_demo = MvrDependency {
+        network: Some("testnet".to_string()),
+        ..dependency("mvr_demo", "@mvr/demo/4")
+    };
+    move_toml.add_dependency(&testnet_demo, true).unwrap();
+    // The network override is read back, so re-adding it is not a conflict either.
+    move_toml.add_dependency(&testnet_demo, false).unwrap();
+    move_toml
+        .add_dependency(&dependency("demo_alias", "@mvr/demo"), false)
+        .unwrap();
+
+    assert!(move_toml.get_mvr_dependencies().contains(&testnet_demo));
+    assert_snapshot!(move_toml.doc.to_string());
+}
+
+#[test]
+fn test_write_changes() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("Move.toml");
+    let manifest = "[package]\nname = \"app\"\n\n[dependencies]\n";
+    fs::write(&path, manifest).unwrap();
+
+    let mut move_toml = MoveToml::new(path.clone()).unwrap();
+    assert!(move_toml.diff().is_none());
+
+    move_toml
+        .add_dependency(&dependency("demo", "@mvr/demo"), false)
+        .unwrap();
+
+    // A dry run returns the diff, without writing the file.
+    let dry_run = WriteOptions {
+        dry_run: true,
+        backup: false,
+    };
+    let diff = move_toml.write_changes(&dry_run).unwrap().unwrap();
+    assert!(diff.contains("+demo = { r.mvr = \"@mvr/demo\" }"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), manifest);
+
+    let backup = WriteOptions {
+        dry_run: false,
+        backup: true,
+    };
+    move_toml.write_changes(&backup).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        move_toml.doc.to_string()
+    );
+    assert_eq!(backup_path(&path), dir.path().join("Move.toml.bak"));
+    assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), manifest);
+
+    // No temporary files are left behind.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+}
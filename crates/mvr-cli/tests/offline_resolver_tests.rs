@@ -0,0 +1,124 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpListener,
+    sync::Arc,
+    thread,
+};
+
+use mvr::{
+    types::{
+        api_types::{GitInfo, PackageRequest},
+        resolver_alt::resolve_batch,
+        Network,
+    },
+    utils::{
+        lockfile::MvrLock,
+        package_cache::set_offline_mode,
+        sui_binary::{with_sui_binary, SuiOutput},
+        sui_fixtures::ScriptedSuiBinary,
+    },
+};
+use tempfile::TempDir;
+
+const MAINNET_CHAIN_ID: &str = "35834a8a";
+
+const RESOLVE_REQUEST: &str = r#"[{"jsonrpc":"2.0","id":1,"method":"resolve","params":{"env":"35834a8a","data":"@mvr/demo"}}]"#;
+
+/// Serves `@mvr/demo` (whose latest version is 3) from a local MVR API, and returns its URL.
+fn serve_api() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let package = PackageRequest {
+        name: "@mvr/demo".to_string(),
+        metadata: serde_json::Value::Null,
+        package_info: None,
+        git_info: Some(GitInfo {
+            repository_url: Some("https://github.com/MystenLabs/mvr".to_string()),
+            path: Some("packages/tests/demo".to_string()),
+            tag: Some("main".to_string()),
+        }),
+        version: 3,
+        package_address: "0x1".to_string(),
+    };
+
+    thread::spawn(move || {
+        for mut socket in listener.incoming().flatten() {
+            let mut buf = vec![0; 8192];
+            let n = socket.read(&mut buf).unwrap_or_default();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+
+            // Only the unversioned name is served, so the pinned one can only come from the cache.
+            let response = if request.starts_with("GET /v1/names/@mvr/demo ") {
+                let body = serde_json::to_string(&package).unwrap();
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+
+            let _ = socket.write_all(response.as_bytes());
+        }
+    });
+
+    url
+}
+
+fn sui() -> Arc<ScriptedSuiBinary> {
+    Arc::new(
+        ScriptedSuiBinary::new()
+            .on(&["--version"], SuiOutput::ok("sui 1.63.0-abc123\n"))
+            .on(
+                &["client", "chain-identifier"],
+                SuiOutput::ok(format!("{MAINNET_CHAIN_ID}\n")),
+            )
+            .on(
+                &["move", "cache-package"],
+                SuiOutput::ok(format!(
+                    r#"{{"name":"demo","published-at":"0x1","original-id":"0x1","chain_id":"{MAINNET_CHAIN_ID}"}}"#
+                )),
+            ),
+    )
+}
+
+#[tokio::test]
+async fn test_resolve_online_then_offline_with_lock_file() {
+    let dir = TempDir::new().unwrap();
+    std::env::set_var("MVR_CACHE_DIR", dir.path().join("cache"));
+    std::env::set_var("MVR_CONFIG", dir.path().join("config.toml"));
+    std::env::set_var("MVR_NETWORK_MAINNET_API_URL", serve_api());
+
+    let package = dir.path().join("app");
+    fs::create_dir(&package).unwrap();
+    fs::write(
+        package.join("Move.toml"),
+        "[package]\nname = \"app\"\n\n[dependencies]\n",
+    )
+    .unwrap();
+
+    let resolve = || {
+        with_sui_binary(
+            sui(),
+            resolve_batch(RESOLVE_REQUEST, false, Some(package.clone())),
+        )
+    };
+
+    // The first (online) run resolves the latest version, and locks it.
+    let online: serde_json::Value = serde_json::from_str(&resolve().await.unwrap()).unwrap();
+    assert_eq!(online[0]["result"]["rev"], "main");
+
+    let lock = MvrLock::read_from_file(&MvrLock::path_for(&package)).unwrap();
+    assert_eq!(lock.get(&Network::Mainnet, "@mvr/demo").unwrap().version, 3);
+
+    // The offline run resolves the locked version (`@mvr/demo/3`) from the cache.
+    set_offline_mode(true);
+    let offline: serde_json::Value = serde_json::from_str(&resolve().await.unwrap()).unwrap();
+    set_offline_mode(false);
+
+    assert_eq!(offline, online);
+}
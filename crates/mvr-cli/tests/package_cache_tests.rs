@@ -0,0 +1,131 @@
+use std::str::FromStr;
+
+use mvr::{
+    errors::CliError,
+    types::{
+        api_data::{query_multiple_dependencies, query_package},
+        api_types::{GitInfo, PackageRequest},
+        MoveRegistryDependencies, Network,
+    },
+    utils::{
+        package_cache::{set_offline_mode, PackageCache},
+        sui_binary::SuiCachePackageResponse,
+    },
+};
+use sui_sdk_types::Address;
+use tempfile::TempDir;
+
+fn package_request(version: u64) -> PackageRequest {
+    PackageRequest {
+        name: "@mvr/demo".to_string(),
+        metadata: serde_json::Value::Null,
+        package_info: None,
+        git_info: Some(GitInfo {
+            repository_url: Some("https://github.com/MystenLabs/mvr".to_string()),
+            path: Some("packages/tests/demo".to_string()),
+            tag: Some("main".to_string()),
+        }),
+        version,
+        package_address: format!("0x{version}"),
+    }
+}
+
+#[test]
+fn test_package_cache_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let cache = PackageCache::new(dir.path().to_path_buf());
+
+    assert!(cache
+        .get_package_request(&Network::Mainnet, "@mvr/demo")
+        .unwrap()
+        .is_none());
+
+    cache
+        .put_package_request(&Network::Mainnet, "@mvr/demo", &package_request(2))
+        .unwrap();
+    cache
+        .put_package_request(&Network::Mainnet, "@mvr/demo/2", &package_request(2))
+        .unwrap();
+
+    let cached = cache
+        .get_package_request(&Network::Mainnet, "@mvr/demo")
+        .unwrap()
+        .unwrap();
+    assert_eq!(cached.version, 2);
+
+    // Entries are kept per network.
+    assert!(cache
+        .get_package_request(&Network::Testnet, "@mvr/demo")
+        .unwrap()
+        .is_none());
+
+    // Identical content is only stored once.
+    assert_eq!(
+        std::fs::read_dir(dir.path().join("objects"))
+            .unwrap()
+            .count(),
+        1
+    );
+
+    let cached_package = SuiCachePackageResponse {
+        name: "demo".to_string(),
+        published_at: Address::from_str("0x2").unwrap(),
+        original_id: Address::from_str("0x1").unwrap(),
+        chain_id: "35834a8a".to_string(),
+    };
+
+    cache
+        .put_cached_package(&Network::Mainnet, "@mvr/demo/2", &cached_package)
+        .unwrap();
+
+    let cached = cache
+        .get_cached_package(&Network::Mainnet, "@mvr/demo/2")
+        .unwrap()
+        .unwrap();
+    assert_eq!(cached.published_at, cached_package.published_at);
+}
+
+#[tokio::test]
+async fn test_offline_query_package() {
+    let dir = TempDir::new().unwrap();
+    std::env::set_var("MVR_CACHE_DIR", dir.path());
+    set_offline_mode(true);
+
+    PackageCache::from_env()
+        .unwrap()
+        .put_package_request(&Network::Testnet, "@mvr/demo/1", &package_request(1))
+        .unwrap();
+
+    let (_, package) = query_package("@mvr/demo/1", &Network::Testnet)
+        .await
+        .unwrap();
+    assert_eq!(package.package_address, "0x1");
+
+    let err = query_package("@mvr/demo", &Network::Testnet)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<CliError>().unwrap(),
+        CliError::NotCachedOffline("@mvr/demo".to_string(), "testnet".to_string())
+    );
+    // Resolving multiple names reports every name that failed, not just the first one.
+    let err = query_multiple_dependencies(
+        MoveRegistryDependencies {
+            packages: vec![
+                "@mvr/demo/1".to_string(),
+                "@mvr/demo".to_string(),
+                "@mvr/core".to_string(),
+            ],
+        },
+        &Network::Testnet,
+        false,
+    )
+    .await
+    .unwrap_err()
+    .to_string();
+
+    assert!(err.contains("Missing 2 packages"));
+    assert!(err.contains("- @mvr/demo: "));
+    assert!(err.contains("- @mvr/core: "));
+    assert!(!err.contains("@mvr/demo/1"));
+}
@@ -0,0 +1,161 @@
+use std::{str::FromStr, time::Duration};
+
+use mvr::{
+    types::{
+        api_data::{query_package, reverse_resolve_addresses},
+        api_types::PackageRequest,
+        CachedResolutionKind, Network,
+    },
+    utils::resolution_cache::ResolutionCache,
+};
+use mvr_types::name::VersionedName;
+use sui_sdk_types::Address;
+use tempfile::TempDir;
+
+fn package_request(version: u64) -> PackageRequest {
+    PackageRequest {
+        name: "@mvr/demo".to_string(),
+        metadata: serde_json::Value::Null,
+        package_info: None,
+        git_info: None,
+        version,
+        package_address: format!("0x{version}"),
+    }
+}
+
+fn name(name: &str) -> VersionedName {
+    VersionedName::from_str(name).unwrap()
+}
+
+#[test]
+fn test_pinned_names_are_cached_indefinitely() {
+    let dir = TempDir::new().unwrap();
+    // A TTL of zero means that only pinned names are ever answered from the cache.
+    let cache = ResolutionCache::new(dir.path().to_path_buf(), Duration::ZERO);
+
+    cache
+        .put_packages(
+            &Network::Mainnet,
+            [
+                (&name("@mvr/demo/1"), &package_request(1)),
+                (&name("@mvr/demo"), &package_request(2)),
+            ],
+        )
+        .unwrap();
+
+    let pinned = cache
+        .get_package(&Network::Mainnet, &name("@mvr/demo/1"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(pinned.version, 1);
+    assert!(cache
+        .get_package(&Network::Mainnet, &name("@mvr/demo"))
+        .unwrap()
+        .is_none());
+
+    // Expired results are dropped on the next write.
+    let entries = cache.entries(None).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, "@mvr/demo/1");
+    assert_eq!(entries[0].expires_at, None);
+}
+
+#[test]
+fn test_latest_names_and_addresses_are_cached_for_ttl() {
+    let dir = TempDir::new().unwrap();
+    let cache = ResolutionCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    let address = Address::from_str("0x2").unwrap();
+
+    cache
+        .put_packages(
+            &Network::Testnet,
+            [(&name("@mvr/demo"), &package_request(2))],
+        )
+        .unwrap();
+    cache
+        .put_names(
+            &Network::Testnet,
+            [(&address, &Some("@mvr/demo/2".to_string()))],
+        )
+        .unwrap();
+
+    assert_eq!(
+        cache
+            .get_package(&Network::Testnet, &name("@mvr/demo"))
+            .unwrap()
+            .unwrap()
+            .version,
+        2
+    );
+    assert_eq!(
+        cache.get_name(&Network::Testnet, &address).unwrap(),
+        Some(Some("@mvr/demo/2".to_string()))
+    );
+
+    // Results are kept per network.
+    assert!(cache
+        .get_package(&Network::Mainnet, &name("@mvr/demo"))
+        .unwrap()
+        .is_none());
+
+    // A new cache (e.g. of a later invocation) reads the results from disk.
+    let entries = ResolutionCache::new(dir.path().to_path_buf(), Duration::from_secs(60))
+        .entries(Some(&Network::Testnet))
+        .unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries
+        .iter()
+        .all(|entry| entry.network == "testnet" && entry.expires_at.is_some()));
+    assert_eq!(entries[1].kind, CachedResolutionKind::ReverseResolution);
+    assert_eq!(entries[1].value.as_deref(), Some("@mvr/demo/2"));
+}
+
+#[test]
+fn test_clear() {
+    let dir = TempDir::new().unwrap();
+    let cache = ResolutionCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+
+    for network in [Network::Mainnet, Network::Testnet] {
+        cache
+            .put_packages(&network, [(&name("@mvr/demo/1"), &package_request(1))])
+            .unwrap();
+    }
+
+    assert_eq!(cache.clear(Some(&Network::Testnet)).unwrap(), 1);
+    assert!(cache
+        .get_package(&Network::Testnet, &name("@mvr/demo/1"))
+        .unwrap()
+        .is_none());
+    assert!(cache
+        .get_package(&Network::Mainnet, &name("@mvr/demo/1"))
+        .unwrap()
+        .is_some());
+
+    assert_eq!(cache.clear(None).unwrap(), 1);
+    assert!(cache.entries(None).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_queries_are_answered_from_cache() {
+    let dir = TempDir::new().unwrap();
+    std::env::set_var("MVR_CACHE_DIR", dir.path());
+
+    // The network has no API configured, so any request would fail.
+    let network = Network::Custom("unconfigured".to_string());
+    let address = Address::from_str("0x1").unwrap();
+
+    let cache = ResolutionCache::from_env().unwrap();
+    cache
+        .put_packages(&network, [(&name("@mvr/demo/1"), &package_request(1))])
+        .unwrap();
+    cache.put_names(&network, [(&address, &None)]).unwrap();
+
+    let (_, package) = query_package("@mvr/demo/1", &network).await.unwrap();
+    assert_eq!(package.package_address, "0x1");
+    assert!(query_package("@mvr/demo/2", &network).await.is_err());
+
+    let names = reverse_resolve_addresses(&[address], &network)
+        .await
+        .unwrap();
+    assert_eq!(names[&address], None);
+}
@@ -0,0 +1,739 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpListener,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Once},
+    thread,
+};
+
+use mvr::{
+    commands::CommandOutput,
+    errors::CliError,
+    get_pinned_dependencies, subcommand_name_info, subcommand_search_names,
+    types::{
+        api_data::query_version_history,
+        api_types::{GitInfo, PackageRequest},
+        resolver_alt::resolve_batch,
+        AddOptions, Network, WriteOptions,
+    },
+    update_mvr_packages, update_pinned_dependencies,
+    utils::{
+        lockfile::MvrLock,
+        manifest::MoveToml,
+        sui_binary::{
+            check_sui_version, get_active_network, with_sui_binary, ProcessSuiBinary, SuiBinary,
+            SuiOutput,
+        },
+        sui_fixtures::{ScriptedSuiBinary, SuiFixture, SuiRun},
+    },
+};
+use mvr_types::name::VersionedName;
+use semver::Version;
+use tempfile::TempDir;
+
+const MAINNET_CHAIN_ID: &str = "35834a8a";
+
+static SETUP: Once = Once::new();
+
+/// Points the CLI to a local MVR API (which knows `@mvr/demo`), and to an empty cache and config.
+fn setup() {
+    SETUP.call_once(|| {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for mut socket in listener.incoming().flatten() {
+                let mut buf = vec![0; 8192];
+                let n = socket.read(&mut buf).unwrap_or_default();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+
+                // The bulk endpoint is not supported, so names are looked up one by one.
+                let body = if request.starts_with("GET /v1/names/@mvr/demo ")
+                    || request.starts_with("GET /v1/names/@mvr/demo/1 ")
+                {
+                    Some(serde_json::to_value(package_request()).unwrap())
+                } else if request.starts_with("GET /v1/names/@mvr/no-source ") {
+                    // A package without a source link.
+                    Some(serde_json::to_value(PackageRequest {
+                        name: "@mvr/no-source".to_string(),
+                        git_info: None,
+                        package_address: "0x2".to_string(),
+                        ..package_request()
+                    }).unwrap())
+                } else if let Some(version) = ["1", "3"].into_iter().find(|version| {
+                    request.starts_with(&format!("GET /v1/names/@mvr/history/{version} "))
+                }) {
+                    // A name whose second version cannot be queried.
+                    Some(serde_json::to_value(PackageRequest {
+                        name: "@mvr/history".to_string(),
+                        version: version.parse().unwrap(),
+                        ..package_request()
+                    }).unwrap())
+                } else if request.starts_with("GET /v1/names/@mvr/pinned ") {
+                    // A name whose latest version is 5.
+                    Some(serde_json::to_value(PackageRequest {
+                        name: "@mvr/pinned".to_string(),
+                        version: 5,
+                        ..package_request()
+                    }).unwrap())
+                } else if request.starts_with("GET /v1/names?") {
+                    // Names of several organizations, as returned by an API ignoring `org`.
+                    let names = ["@mvr/demo", "@other/mvr-demo", "@mvr/core"].map(|name| {
+                        serde_json::json!({ "name": name, "metadata": {} })
+                    });
+                    Some(serde_json::json!({ "data": names, "next_cursor": null, "limit": 10 }))
+                } else {
+                    None
+                };
+
+                let response = match body {
+                    Some(body) => {
+                        let body = body.to_string();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                    }
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string(),
+                };
+
+                let _ = socket.write_all(response.as_bytes());
+            }
+        });
+
+        let dir = TempDir::new().unwrap().keep();
+        std::env::set_var("MVR_CACHE_DIR", dir.join("cache"));
+        std::env::set_var("MVR_CONFIG", dir.join("config.toml"));
+        std::env::set_var("MVR_NETWORK_MAINNET_API_URL", &url);
+        std::env::set_var("MVR_NETWORK_TESTNET_API_URL", &url);
+    });
+}
+
+fn package_request() -> PackageRequest {
+    PackageRequest {
+        name: "@mvr/demo".to_string(),
+        metadata: serde_json::Value::Null,
+        package_info: None,
+        git_info: Some(GitInfo {
+            repository_url: Some("https://github.com/MystenLabs/mvr".to_string()),
+            path: Some("packages/tests/demo".to_string()),
+            tag: Some("main".to_string()),
+        }),
+        version: 1,
+        package_address: "0x1".to_string(),
+    }
+}
+
+fn cache_package_output(published_at: &str, original_id: &str) -> SuiOutput {
+    SuiOutput::ok(format!(
+        r#"{{"name":"demo","published-at":"{published_at}","original-id":"{original_id}","chain_id":"{MAINNET_CHAIN_ID}"}}"#
+    ))
+}
+
+fn sui(published_at: &str, original_id: &str) -> Arc<ScriptedSuiBinary> {
+    Arc::new(
+        ScriptedSuiBinary::new()
+            .on(&["--version"], SuiOutput::ok("sui 1.63.0-abc123\n"))
+            .on(
+                &["client", "chain-identifier"],
+                SuiOutput::ok(format!("{MAINNET_CHAIN_ID}\n")),
+            )
+            .on(
+                &["move", "cache-package"],
+                cache_package_output(published_at, original_id),
+            ),
+    )
+}
+
+fn write_package(root: &Path) -> MoveToml {
+    fs::write(
+        root.join("Move.toml"),
+        "[package]\nname = \"app\"\n\n[dependencies]\n",
+    )
+    .unwrap();
+    MoveToml::new(root.join("Move.toml")).unwrap()
+}
+
+const NO_FORCE: AddOptions = AddOptions {
+    alias: None,
+    version: None,
+    network: None,
+    force: false,
+    write: WriteOptions {
+        dry_run: false,
+        backup: false,
+    },
+};
+const FORCE: AddOptions = AddOptions {
+    force: true,
+    ..NO_FORCE
+};
+
+const RESOLVE_REQUEST: &str = r#"[{"jsonrpc":"2.0","id":1,"method":"resolve","params":{"env":"35834a8a","data":"@mvr/demo"}}]"#;
+
+#[tokio::test]
+async fn test_scripted_version_and_network() {
+    let old_sui = Arc::new(
+        ScriptedSuiBinary::new()
+            .on(&["--version"], SuiOutput::ok("sui 1.62.1-abc123\n"))
+            .on(&["client", "chain-identifier"], SuiOutput::ok("4c78adac\n")),
+    );
+
+    let (version_check, network) = with_sui_binary(old_sui.clone(), async {
+        (
+            check_sui_version(&Version::new(1, 63, 0)),
+            get_active_network(),
+        )
+    })
+    .await;
+
+    assert_eq!(
+        version_check.unwrap_err().downcast::<CliError>().unwrap(),
+        CliError::SuiVersionTooLow {
+            found: "1.62.1-abc123".to_string(),
+            required: "1.63.0".to_string(),
+        }
+    );
+    assert_eq!(network.unwrap(), Network::Testnet);
+    assert_eq!(
+        old_sui.calls(),
+        vec![vec!["--version"], vec!["client", "chain-identifier"]]
+    );
+
+    // Unscripted commands fail, like a failing `sui` command would.
+    let output = old_sui.run(&["move", "build"]).unwrap();
+    assert!(!output.is_success());
+    assert_eq!(output.stderr, "No scripted output for `sui move build`");
+}
+
+#[tokio::test]
+async fn test_update_mvr_packages() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    let move_toml = write_package(dir.path());
+
+    let sui = sui("0x1", "0x1");
+    let output = with_sui_binary(
+        sui.clone(),
+        update_mvr_packages(
+            vec![move_toml],
+            &["@mvr/demo".to_string()],
+            &Network::Mainnet,
+            &AddOptions::default(),
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert!(output.contains("Successfully added dependency"));
+    assert!(fs::read_to_string(dir.path().join("Move.toml"))
+        .unwrap()
+        .contains(r#"demo = { r.mvr = "@mvr/demo" }"#));
+
+    let calls = sui.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(
+        calls[0][..4],
+        ["move", "cache-package", "mainnet", MAINNET_CHAIN_ID]
+    );
+    assert!(calls[0][4].contains("git = \"https://github.com/MystenLabs/mvr\""));
+
+    // A dry run prints the diff, but leaves the Move.toml untouched.
+    let dir = TempDir::new().unwrap();
+    let move_toml = write_package(dir.path());
+    let before = move_toml.doc.to_string();
+    let options = AddOptions {
+        write: WriteOptions {
+            dry_run: true,
+            backup: false,
+        },
+        ..AddOptions::default()
+    };
+
+    let output = with_sui_binary(
+        sui.clone(),
+        update_mvr_packages(
+            vec![move_toml],
+            &["@mvr/demo".to_string()],
+            &Network::Mainnet,
+            &options,
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert!(output.contains(r#"+demo = { r.mvr = "@mvr/demo" }"#));
+    assert!(output.contains("Would add dependency"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("Move.toml")).unwrap(),
+        before
+    );
+}
+
+#[tokio::test]
+async fn test_update_mvr_packages_with_options() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    let options = AddOptions {
+        alias: Some("my_demo".to_string()),
+        version: Some(1),
+        network: Some(Network::Testnet),
+        ..NO_FORCE
+    };
+
+    let sui = sui("0x1", "0x1");
+    with_sui_binary(
+        sui.clone(),
+        update_mvr_packages(
+            vec![write_package(dir.path())],
+            &["@mvr/demo".to_string()],
+            &Network::Testnet,
+            &options,
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert!(fs::read_to_string(dir.path().join("Move.toml"))
+        .unwrap()
+        .contains(r#"my_demo = { r.mvr.name = "@mvr/demo/1", r.mvr.network = "testnet" }"#));
+    assert_eq!(sui.calls()[0][2], "testnet");
+
+    // An alias or version cannot be shared by several names.
+    let err = update_mvr_packages(
+        vec![write_package(dir.path())],
+        &["@mvr/demo".to_string(), "@mvr/other".to_string()],
+        &Network::Testnet,
+        &options,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("when adding a single dependency"));
+
+    // A pinned name conflicting with `--version`.
+    let err = update_mvr_packages(
+        vec![write_package(dir.path())],
+        &["@mvr/demo/2".to_string()],
+        &Network::Testnet,
+        &options,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("already pinned to version 2"));
+}
+
+#[tokio::test]
+async fn test_update_mvr_packages_with_conflicts() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    let app = dir.path().join("app");
+    let local = dir.path().join("local_demo");
+    fs::create_dir_all(&app).unwrap();
+    fs::create_dir_all(&local).unwrap();
+
+    // A local copy of `@mvr/demo`, at another version.
+    fs::write(
+        local.join("Move.toml"),
+        "[package]\nname = \"local_demo\"\n",
+    )
+    .unwrap();
+    fs::write(
+        local.join("Published.toml"),
+        "[published.mainnet]\noriginal-id = \"0x1\"\npublished-at = \"0x3\"\n",
+    )
+    .unwrap();
+
+    let manifest =
+        "[package]\nname = \"app\"\n\n[dependencies]\nlocal_demo = { local = \"../local_demo\" }\n";
+    fs::write(app.join("Move.toml"), manifest).unwrap();
+
+    let names = ["@mvr/demo".to_string()];
+    let add = |force| {
+        update_mvr_packages(
+            vec![MoveToml::new(app.join("Move.toml")).unwrap()],
+            &names,
+            &Network::Mainnet,
+            if force { &FORCE } else { &NO_FORCE },
+        )
+    };
+
+    let err = with_sui_binary(sui("0x1", "0x1"), add(false))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(CliError::DependencyConflicts(conflicts, _)) if conflicts.contains("local_demo")
+    ));
+    // Nothing is written when a conflict is found.
+    assert_eq!(fs::read_to_string(app.join("Move.toml")).unwrap(), manifest);
+
+    with_sui_binary(sui("0x1", "0x1"), add(true)).await.unwrap();
+    assert!(fs::read_to_string(app.join("Move.toml"))
+        .unwrap()
+        .contains("@mvr/demo"));
+}
+
+#[tokio::test]
+async fn test_search_names_by_org() {
+    setup();
+
+    let search = |org: &str| {
+        subcommand_search_names(
+            Some("demo".to_string()),
+            None,
+            None,
+            Some(Network::Mainnet),
+            false,
+            Some(org.to_string()),
+        )
+    };
+
+    for org in ["@mvr", "mvr.sui", "@mvr/"] {
+        let CommandOutput::Search(results) = search(org).await.unwrap() else {
+            panic!("Expected search results");
+        };
+        let names = results
+            .data
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["@mvr/demo", "@mvr/core"], "{org}");
+    }
+
+    let Err(err) = search("not an org").await else {
+        panic!("Expected an invalid organization");
+    };
+    assert!(err.to_string().contains("Invalid organization"));
+}
+
+#[tokio::test]
+async fn test_version_history() {
+    setup();
+
+    let name = VersionedName::from_str("@mvr/history").unwrap();
+    let (versions, missing_versions) = query_version_history(&name, 3, &Network::Mainnet)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        versions.iter().map(|v| v.version).collect::<Vec<_>>(),
+        [1, 3]
+    );
+    assert_eq!(missing_versions, [2]);
+
+    // The API does not return more than 50 dependents at once.
+    for dependents in [0, 51] {
+        let Err(err) =
+            subcommand_name_info("@mvr/history", Some(Network::Mainnet), dependents).await
+        else {
+            panic!("Expected an invalid `--dependents`");
+        };
+        assert!(err.to_string().contains("must be between 1 and 50"));
+    }
+}
+
+#[tokio::test]
+async fn test_outdated_and_update_pinned_dependencies() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("Move.toml");
+    let manifest = r#"[package]
+name = "app"
+
+[dependencies]
+pinned = { r.mvr = "@mvr/pinned/2" } # keep me
+with_network = { r.mvr.name = "@mvr/pinned/3", r.mvr.network = "mainnet", override = true }
+latest = { r.mvr = "@mvr/pinned" }
+"#;
+    fs::write(&path, manifest).unwrap();
+
+    // Only pinned dependencies are listed, along with their latest version.
+    let move_toml = MoveToml::new(path.clone()).unwrap();
+    let pinned = get_pinned_dependencies(&move_toml, None, &Network::Mainnet)
+        .await
+        .unwrap();
+    assert_eq!(
+        pinned
+            .iter()
+            .map(|dep| (
+                dep.dependency.alias.as_str(),
+                dep.pinned_version,
+                dep.latest_version,
+                dep.is_outdated()
+            ))
+            .collect::<Vec<_>>(),
+        [("pinned", 2, 5, true), ("with_network", 3, 5, true)]
+    );
+
+    // `--max-version` limits how far pins get bumped, and pins at (or above) it are kept.
+    let mut move_toml = MoveToml::new(path.clone()).unwrap();
+    let updated = update_pinned_dependencies(&mut move_toml, None, &Network::Mainnet, Some(3))
+        .await
+        .unwrap();
+    assert_eq!(
+        updated
+            .iter()
+            .map(|dep| (dep.from.as_str(), dep.to.as_str()))
+            .collect::<Vec<_>>(),
+        [("@mvr/pinned/2", "@mvr/pinned/3")]
+    );
+
+    // A dry run only returns the diff.
+    let diff = move_toml
+        .write_changes(&WriteOptions {
+            dry_run: true,
+            backup: false,
+        })
+        .unwrap()
+        .unwrap();
+    assert!(diff.contains(r#"-pinned = { r.mvr = "@mvr/pinned/2" } # keep me"#));
+    assert!(diff.contains(r#"+pinned = { r.mvr = "@mvr/pinned/3" } # keep me"#));
+    assert_eq!(fs::read_to_string(&path).unwrap(), manifest);
+
+    // Only the names are rewritten, keeping the rest of the dependencies.
+    let mut move_toml = MoveToml::new(path.clone()).unwrap();
+    update_pinned_dependencies(&mut move_toml, None, &Network::Mainnet, None)
+        .await
+        .unwrap();
+    move_toml.write_changes(&WriteOptions::default()).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        manifest
+            .replace("@mvr/pinned/2", "@mvr/pinned/5")
+            .replace("@mvr/pinned/3", "@mvr/pinned/5")
+    );
+}
+
+#[tokio::test]
+async fn test_resolver_with_lock_file() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    write_package(dir.path());
+
+    let response = with_sui_binary(
+        sui("0x1", "0x1"),
+        resolve_batch(RESOLVE_REQUEST, false, Some(dir.path().to_path_buf())),
+    )
+    .await
+    .unwrap();
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(
+        response[0]["result"],
+        serde_json::json!({
+            "git": "https://github.com/MystenLabs/mvr",
+            "rev": "main",
+            "subdir": "packages/tests/demo"
+        })
+    );
+
+    let lock = MvrLock::read_from_file(&MvrLock::path_for(dir.path())).unwrap();
+    assert_eq!(lock.get(&Network::Mainnet, "@mvr/demo").unwrap().version, 1);
+
+    // The cached package no longer matches the address of the name.
+    let response = with_sui_binary(
+        sui("0x2", "0x2"),
+        resolve_batch(RESOLVE_REQUEST, false, Some(dir.path().to_path_buf())),
+    )
+    .await
+    .unwrap();
+    assert!(response.contains("has an address missmatch"));
+
+    // The package matches its address, but not the one recorded in the lock file.
+    let response = with_sui_binary(
+        sui("0x1", "0x5"),
+        resolve_batch(RESOLVE_REQUEST, false, Some(dir.path().to_path_buf())),
+    )
+    .await
+    .unwrap();
+    assert!(response.contains("does not match the version recorded in"));
+
+    // ... unless the lock file is updated.
+    let response = with_sui_binary(
+        sui("0x1", "0x5"),
+        resolve_batch(RESOLVE_REQUEST, true, Some(dir.path().to_path_buf())),
+    )
+    .await
+    .unwrap();
+    assert!(response.contains("\"result\""));
+}
+
+#[tokio::test]
+async fn test_resolver_per_request_errors() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    write_package(dir.path());
+
+    // A (multi-line) batch mixing networks, valid and invalid requests.
+    let request = r#"[
+        {"jsonrpc":"2.0","id":1,"method":"resolve","params":{"env":"35834a8a","data":"@mvr/demo"}},
+        {"jsonrpc":"2.0","id":2,"method":"publish","params":{}},
+        {"jsonrpc":"2.0","id":3,"method":"resolve","params":{"env":"35834a8a"}},
+        {"jsonrpc":"2.0","id":4,"method":"resolve","params":{"env":"35834a8a","data":"@mvr/missing"}},
+        {"jsonrpc":"2.0","id":5,"method":"resolve","params":{"env":"4c78adac","data":"@mvr/demo"}},
+        {"jsonrpc":"2.0","id":6,"method":"resolve","params":{"env":"deadbeef","data":"@mvr/demo"}}
+    ]"#;
+
+    let response = with_sui_binary(
+        sui("0x1", "0x1"),
+        resolve_batch(request, false, Some(dir.path().to_path_buf())),
+    )
+    .await
+    .unwrap();
+
+    let responses: Vec<serde_json::Value> = serde_json::from_str(&response).unwrap();
+    let outcomes = responses
+        .iter()
+        .map(|response| {
+            (
+                response["id"].as_u64().unwrap(),
+                response["error"]["code"].as_i64(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        outcomes,
+        vec![
+            (1, None),
+            (2, Some(-32601)),
+            (3, Some(-32602)),
+            (4, Some(404)),
+            (5, None),
+            (6, Some(-32602)),
+        ]
+    );
+    assert_eq!(responses[0]["result"]["rev"], "main");
+    assert_eq!(responses[4]["result"]["rev"], "main");
+
+    // Both networks are recorded in the lock file.
+    let lock = MvrLock::read_from_file(&MvrLock::path_for(dir.path())).unwrap();
+    assert!(lock.get(&Network::Mainnet, "@mvr/demo").is_some());
+    assert!(lock.get(&Network::Testnet, "@mvr/demo").is_some());
+}
+
+#[tokio::test]
+async fn test_resolver_network_override() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    write_package(dir.path());
+
+    // A mainnet build, with a dependency resolved on testnet (and one with an unknown network).
+    let request = r#"[
+        {"jsonrpc":"2.0","id":1,"method":"resolve","params":{"env":"35834a8a","data":{"name":"@mvr/demo","network":"testnet"}}},
+        {"jsonrpc":"2.0","id":2,"method":"resolve","params":{"env":"35834a8a","data":{"name":"@mvr/demo","network":"unknown"}}}
+    ]"#;
+
+    let response = with_sui_binary(
+        sui("0x1", "0x1"),
+        resolve_batch(request, false, Some(dir.path().to_path_buf())),
+    )
+    .await
+    .unwrap();
+
+    let responses: Vec<serde_json::Value> = serde_json::from_str(&response).unwrap();
+    assert_eq!(responses[0]["result"]["rev"], "main");
+    assert_eq!(responses[1]["error"]["code"], -32602);
+
+    let lock = MvrLock::read_from_file(&MvrLock::path_for(dir.path())).unwrap();
+    assert!(lock.get(&Network::Testnet, "@mvr/demo").is_some());
+    assert!(lock.get(&Network::Mainnet, "@mvr/demo").is_none());
+}
+
+#[tokio::test]
+async fn test_resolver_without_git_sources() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    write_package(dir.path());
+
+    let request = r#"[{"jsonrpc":"2.0","id":1,"method":"resolve","params":{"env":"35834a8a","data":"@mvr/no-source"}}]"#;
+
+    let sui = sui("0x1", "0x1");
+    let response = with_sui_binary(
+        sui.clone(),
+        resolve_batch(request, false, Some(dir.path().to_path_buf())),
+    )
+    .await
+    .unwrap();
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response[0]["error"]["code"], 404);
+    assert!(response[0]["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("does not have git info for env mainnet"));
+
+    // Nothing is cached or locked for packages that cannot be resolved.
+    assert!(sui.calls().is_empty());
+    assert!(!MvrLock::path_for(dir.path()).exists());
+}
+
+#[tokio::test]
+async fn test_resolver_invalid_input() {
+    setup();
+    let dir = TempDir::new().unwrap();
+    write_package(dir.path());
+
+    for (input, code) in [
+        ("[{\"jsonrpc\":", -32700),
+        ("\"resolve\"", -32600),
+        ("[]", -32600),
+        ("", -32600),
+    ] {
+        let response = resolve_batch(input, false, Some(dir.path().to_path_buf()))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+        assert_eq!(response["id"], 0, "{input}");
+        assert_eq!(response["error"]["code"], code, "{input}");
+    }
+}
+
+#[tokio::test]
+async fn test_fixture_record_and_replay() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("sui.json");
+
+    // Record a run of a (fake) `sui` binary that prints to both stdout and stderr.
+    let script = dir.path().join("sui");
+    fs::write(
+        &script,
+        "#!/bin/sh\necho \"sui $@\"\necho warning >&2\nexit 3\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    std::env::set_var("SUI_BINARY_PATH", &script);
+    std::env::set_var("MVR_SUI_RECORD_FILE", &path);
+    let output = ProcessSuiBinary::default().run(&["move", "build"]).unwrap();
+    std::env::remove_var("MVR_SUI_RECORD_FILE");
+    std::env::remove_var("SUI_BINARY_PATH");
+
+    let expected = SuiOutput {
+        status: Some(3),
+        stdout: "sui move build\n".to_string(),
+        stderr: "warning\n".to_string(),
+    };
+    assert_eq!(output, expected);
+    assert_eq!(
+        SuiFixture::read_from_file(&path).unwrap().runs,
+        vec![SuiRun {
+            args: vec!["move".to_string(), "build".to_string()],
+            output: expected.clone(),
+        }]
+    );
+
+    let replayed = ScriptedSuiBinary::from_fixture(&path).unwrap();
+    assert_eq!(replayed.run(&["move", "build"]).unwrap(), expected);
+    assert!(!replayed.run(&["--version"]).unwrap().is_success());
+}
@@ -0,0 +1,63 @@
+use mvr::{
+    constants::MINIMUM_BUILD_SUI_VERSION,
+    errors::CliError,
+    utils::sui_binary::{is_supported_version, parse_sui_version},
+};
+use semver::Version;
+
+#[test]
+fn test_parse_sui_version() {
+    let version = parse_sui_version("sui 1.63.0-0362997459\n").unwrap();
+    assert_eq!((version.major, version.minor, version.patch), (1, 63, 0));
+    // Git revisions with leading zeros are not valid pre-releases, so they end up as build metadata.
+    assert_eq!(version.build.as_str(), "0362997459");
+    // ... as do other git revisions, even when they are valid pre-releases.
+    let version = parse_sui_version("sui 1.63.0-abc123").unwrap();
+    assert!(version.pre.is_empty());
+    assert_eq!(version.build.as_str(), "abc123");
+
+    let version = parse_sui_version("sui 1.63.0-rc.1").unwrap();
+    assert_eq!(version.pre.as_str(), "rc.1");
+
+    let version = parse_sui_version("sui 1.64.0-nightly").unwrap();
+    assert_eq!(version.pre.as_str(), "nightly");
+
+    assert_eq!(
+        parse_sui_version("sui 2.0.0").unwrap(),
+        Version::new(2, 0, 0)
+    );
+
+    assert_eq!(
+        parse_sui_version("sui unknown").unwrap_err(),
+        CliError::InvalidSuiVersion("sui unknown".to_string())
+    );
+}
+
+#[test]
+fn test_supported_sui_versions() {
+    let supported = |output: &str| {
+        is_supported_version(
+            &parse_sui_version(output).unwrap(),
+            &MINIMUM_BUILD_SUI_VERSION,
+        )
+    };
+
+    assert!(supported("sui 1.63.0"));
+    assert!(supported("sui 1.63.0-0362997459"));
+    assert!(supported("sui 1.63.0-abc123"));
+    assert!(supported("sui 1.63.2"));
+    assert!(supported("sui 1.64.0-nightly"));
+    assert!(supported("sui 1.64.0-rc.1"));
+    assert!(supported("sui 2.0.0"));
+    assert!(supported("sui 2.1.0-rc.1"));
+    // Nightly builds of the minimum version are whitelisted ...
+    assert!(supported("sui 1.63.0-nightly"));
+
+    // ... but other pre-releases of the minimum version are not supported.
+    assert!(!supported("sui 1.63.0-rc.1"));
+    assert!(!supported("sui 1.63.0-beta"));
+    assert!(!supported("sui 1.62.9-nightly"));
+    assert!(!supported("sui 1.62.9"));
+    assert!(!supported("sui 0.99.0"));
+    assert!(!supported("sui 1.9.0"));
+}
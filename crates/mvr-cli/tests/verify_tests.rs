@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use move_binary_format::file_format::{empty_module, Constant, SignatureToken};
+use move_core_types::account_address::AccountAddress;
+use mvr::types::ModuleStatus;
+use mvr::utils::verify::{compare_modules, module_digest};
+use sui_sdk_types::Address;
+
+/// A module with the given self address, and an address constant.
+fn module_with_constant(address: &Address, constant: &Address, body: &[u8]) -> Vec<u8> {
+    let mut module = empty_module();
+    module.address_identifiers[0] = AccountAddress::new(*address.as_bytes());
+    module.constant_pool.push(Constant {
+        type_: SignatureToken::Address,
+        data: constant.as_bytes().to_vec(),
+    });
+    module.constant_pool.push(Constant {
+        type_: SignatureToken::Vector(Box::new(SignatureToken::U8)),
+        data: [&[body.len() as u8][..], body].concat(),
+    });
+
+    let mut bytes = vec![];
+    module
+        .serialize_with_version(module.version, &mut bytes)
+        .unwrap();
+    bytes
+}
+
+fn module(address: &Address, body: &[u8]) -> Vec<u8> {
+    module_with_constant(address, &Address::ZERO, body)
+}
+
+#[test]
+fn test_compare_modules() {
+    let original_id = Address::from_str("0xc1").unwrap();
+
+    let source = BTreeMap::from([
+        ("a".to_string(), module(&Address::ZERO, b"a")),
+        ("b".to_string(), module(&Address::ZERO, b"b")),
+        ("c".to_string(), module(&Address::ZERO, b"c")),
+    ]);
+    let on_chain = BTreeMap::from([
+        ("a".to_string(), module(&original_id, b"a")),
+        ("b".to_string(), module(&original_id, b"changed")),
+        ("d".to_string(), module(&original_id, b"d")),
+    ]);
+
+    let modules = compare_modules(&source, &on_chain, &original_id).unwrap();
+
+    let statuses = modules
+        .iter()
+        .map(|m| (m.module.as_str(), m.status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            ("a", ModuleStatus::Match),
+            ("b", ModuleStatus::Mismatch),
+            ("c", ModuleStatus::MissingOnChain),
+            ("d", ModuleStatus::MissingFromSource),
+        ]
+    );
+
+    // Digests are computed after substituting the package address, so matching modules
+    // report the same digest.
+    assert_eq!(modules[0].source_digest, modules[0].on_chain_digest);
+    assert_eq!(
+        modules[0].on_chain_digest.as_deref(),
+        Some(module_digest(&on_chain["a"]).as_str())
+    );
+    assert_ne!(modules[1].source_digest, modules[1].on_chain_digest);
+    assert_eq!(modules[2].on_chain_digest, None);
+    assert_eq!(modules[3].source_digest, None);
+}
+
+#[test]
+fn test_compare_modules_keeps_address_constants() {
+    let original_id = Address::from_str("0xc1").unwrap();
+
+    // A `@0x0` constant stays `0x0` on-chain, only the self address is the original ID.
+    let source = BTreeMap::from([("a".to_string(), module(&Address::ZERO, b"a"))]);
+    let on_chain = BTreeMap::from([("a".to_string(), module(&original_id, b"a"))]);
+
+    let modules = compare_modules(&source, &on_chain, &original_id).unwrap();
+    assert_eq!(modules[0].status, ModuleStatus::Match);
+
+    // An on-chain constant set to the original ID does not match a `@0x0` constant in the source.
+    let on_chain = BTreeMap::from([(
+        "a".to_string(),
+        module_with_constant(&original_id, &original_id, b"a"),
+    )]);
+
+    let modules = compare_modules(&source, &on_chain, &original_id).unwrap();
+    assert_eq!(modules[0].status, ModuleStatus::Mismatch);
+}
+
+#[test]
+fn test_compare_modules_invalid_bytecode() {
+    let source = BTreeMap::from([("a".to_string(), b"not a module".to_vec())]);
+
+    let err = compare_modules(&source, &BTreeMap::new(), &Address::ZERO).unwrap_err();
+    assert!(err.to_string().contains("module `a`"));
+}
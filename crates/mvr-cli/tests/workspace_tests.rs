@@ -0,0 +1,101 @@
+use std::{fs, path::Path};
+
+use mvr::utils::workspace::{PinConflict, Workspace};
+use tempfile::TempDir;
+
+fn write_package(root: &Path, dir: &str, dependencies: &str) {
+    let package_dir = root.join(dir);
+    fs::create_dir_all(&package_dir).unwrap();
+    fs::write(
+        package_dir.join("Move.toml"),
+        format!("[package]\nname = \"{dir}\"\n\n[dependencies]\n{dependencies}"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_workspace_discovery_and_pin_conflicts() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+
+    write_package(
+        root,
+        "packages/a",
+        "demo = { r.mvr = \"@mvr/demo/1\" }\ncore = { r.mvr = \"@mvr/core\" }\n",
+    );
+    write_package(
+        root,
+        "packages/b",
+        "demo = { r.mvr = \"@mvr/demo/2\" }\ncore = { r.mvr = \"@mvr/core\" }\n",
+    );
+    write_package(root, "packages/c", "");
+    // Build outputs and hidden directories are never part of the workspace.
+    write_package(
+        root,
+        "packages/a/build/dep",
+        "demo = { r.mvr = \"@mvr/demo/3\" }\n",
+    );
+    write_package(root, ".hidden", "demo = { r.mvr = \"@mvr/demo/4\" }\n");
+
+    let workspace = Workspace::discover(root).unwrap();
+
+    assert_eq!(
+        workspace
+            .packages
+            .iter()
+            .map(|p| p
+                .path
+                .clone()
+                .unwrap()
+                .strip_prefix(root)
+                .unwrap()
+                .to_path_buf())
+            .collect::<Vec<_>>(),
+        vec![
+            Path::new("packages/a/Move.toml"),
+            Path::new("packages/b/Move.toml"),
+            Path::new("packages/c/Move.toml"),
+        ]
+    );
+
+    assert_eq!(
+        workspace.mvr_names().into_iter().collect::<Vec<_>>(),
+        vec!["@mvr/core", "@mvr/demo/1", "@mvr/demo/2"]
+    );
+
+    assert_eq!(
+        workspace.pin_conflicts(),
+        vec![PinConflict {
+            name: "@mvr/demo".to_string(),
+            pins: vec![
+                (root.join("packages/a/Move.toml"), "@mvr/demo/1".to_string()),
+                (root.join("packages/b/Move.toml"), "@mvr/demo/2".to_string()),
+            ],
+        }]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinked_directories_are_not_followed() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+
+    write_package(root, "packages/a", "demo = { r.mvr = \"@mvr/demo\" }\n");
+    // A symlink to one of its parent directories would otherwise be walked forever.
+    std::os::unix::fs::symlink(root, root.join("packages/a/loop")).unwrap();
+
+    let workspace = Workspace::discover(root).unwrap();
+
+    assert_eq!(workspace.packages.len(), 1);
+    assert_eq!(
+        workspace.packages[0].path.as_deref(),
+        Some(root.join("packages/a/Move.toml").as_path())
+    );
+}
+
+#[test]
+fn test_empty_workspace() {
+    let dir = TempDir::new().unwrap();
+    assert!(Workspace::discover(dir.path()).is_err());
+}
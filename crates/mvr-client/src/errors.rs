@@ -0,0 +1,39 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MvrClientError {
+    /// The request could not be sent, or no response was received (after all retries).
+    #[error("Failed to query the MVR API: {0}")]
+    Request(#[from] reqwest::Error),
+
+    /// The requested name, package or type does not exist.
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The API deployment does not know the endpoint (e.g. an older deployment).
+    #[error("The MVR API does not support the {0} endpoint. Please make sure it is up to date.")]
+    EndpointNotSupported(String),
+
+    /// The API rejected the request (e.g. invalid input or batch size), or failed to process it.
+    #[error("The MVR API responded with {status}: {message}")]
+    Api { status: u16, message: String },
+
+    /// The response does not match the expected type.
+    #[error("Failed to parse the response of {url}: {message}")]
+    Decode { url: String, message: String },
+}
+
+impl MvrClientError {
+    /// The HTTP status of the response, for errors returned by the API.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            MvrClientError::Request(e) => e.status().map(|status| status.as_u16()),
+            MvrClientError::NotFound(_) => Some(404),
+            MvrClientError::Api { status, .. } => Some(*status),
+            MvrClientError::EndpointNotSupported(_) | MvrClientError::Decode { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,441 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A typed async client for the MVR API.
+//!
+//! Every route of the API has a method, using the request and response types of
+//! [`mvr_types::api`] (the ones the server uses). Bulk methods accept any number of items, and
+//! split them in requests that respect the server's batch size limit. Paginated methods return a
+//! single page, and have an `all_*` variant that follows the cursors until the last page.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), mvr_client::MvrClientError> {
+//! let client = mvr_client::MvrClient::mainnet();
+//! let package = client.package_by_name("@mvr/core").await?;
+//! println!("{}", package.package_address);
+//! # Ok(())
+//! # }
+//! ```
+
+mod errors;
+mod retry;
+
+use std::{collections::HashMap, future::Future, hash::Hash};
+
+use futures::future::try_join_all;
+use mvr_types::api::{
+    BulkNamesRequest, BulkNamesResponse, BulkResolutionRequest, BulkResolutionResponse,
+    BulkReverseResolutionRequest, BulkReverseResolutionResponse, BulkTypesRequest,
+    BulkTypesResponse, DependentsQueryParams, ErrorResponse, NameAnalyticsResponse,
+    NameSearchQueryParams, NameSearchResponse, PackageAnalyticsResponse, PackageByNameResponse,
+    PackageDependencies, PackageDependent, PackageModules, PaginatedResponse, ResolutionResponse,
+    ReverseResolutionResponse, TypeResolutionResponse, MAX_BATCH_SIZE, MAX_PAGE_LIMIT,
+};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use sui_sdk_types::Address;
+
+pub use errors::MvrClientError;
+pub use retry::send_with_retries;
+
+pub type Result<T, E = MvrClientError> = std::result::Result<T, E>;
+
+pub const MAINNET_URL: &str = "https://mainnet.mvr.mystenlabs.com";
+pub const TESTNET_URL: &str = "https://testnet.mvr.mystenlabs.com";
+
+/// The header the MVR API uses to identify the source of a request.
+pub const MVR_SOURCE_HEADER: &str = "Mvr-Source";
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+#[derive(Debug, Clone)]
+pub struct MvrClient {
+    base_url: String,
+    http: Client,
+    batch_size: usize,
+    max_retries: u32,
+}
+
+impl MvrClient {
+    /// Creates a client for the API deployed at `base_url` (e.g. `https://mainnet.mvr.mystenlabs.com`).
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http: Client::new(),
+            batch_size: MAX_BATCH_SIZE,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    pub fn mainnet() -> Self {
+        Self::new(MAINNET_URL)
+    }
+
+    pub fn testnet() -> Self {
+        Self::new(TESTNET_URL)
+    }
+
+    /// Sends the requests with `http` (e.g. to configure timeouts or default headers).
+    pub fn with_http_client(self, http: Client) -> Self {
+        Self { http, ..self }
+    }
+
+    /// Sets the number of items sent in a single bulk request (defaults to the server's limit).
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Sets how many times a request is retried (see [`send_with_retries`]). Defaults to 3.
+    pub fn with_max_retries(self, max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..self
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// `GET /health`: succeeds when the API is up and can reach its database.
+    pub async fn health(&self) -> Result<()> {
+        let url = format!("{}/health", self.base_url);
+        let response = self.send(self.http.get(&url)).await?;
+
+        error_for_status(&url, response).await.map(|_| ())
+    }
+
+    /// `GET /v1/sitemap`: the sitemap (XML) of all names linked to a package.
+    pub async fn sitemap(&self) -> Result<String> {
+        let url = self.url("sitemap");
+        let response = self.send(self.http.get(&url)).await?;
+
+        Ok(error_for_status(&url, response).await?.text().await?)
+    }
+
+    /// `GET /v1/names/{name}`: the package data of a (versioned) name.
+    pub async fn package_by_name(&self, name: &str) -> Result<PackageByNameResponse> {
+        self.get(&format!("names/{}", name)).await
+    }
+
+    /// `POST /v1/names/bulk`: the package data of every name. Names that cannot be found map to `None`.
+    pub async fn bulk_package_by_name(
+        &self,
+        names: &[impl AsRef<str>],
+    ) -> Result<HashMap<String, Option<PackageByNameResponse>>> {
+        let responses: Vec<BulkNamesResponse> = self
+            .post_in_batches("names/bulk", names, |names| BulkNamesRequest {
+                names: to_strings(names),
+            })
+            .await?;
+
+        Ok(responses
+            .into_iter()
+            .flat_map(|response| response.names)
+            .collect())
+    }
+
+    /// `GET /v1/names`: a page of the names matching a search query.
+    pub async fn search_names(
+        &self,
+        params: &NameSearchQueryParams,
+    ) -> Result<PaginatedResponse<NameSearchResponse>> {
+        self.get_with_query("names", params).await
+    }
+
+    /// Every name matching a search query, following the cursors of [`Self::search_names`].
+    pub async fn all_names(
+        &self,
+        search: Option<String>,
+        is_linked: Option<bool>,
+    ) -> Result<Vec<NameSearchResponse>> {
+        collect_pages(|cursor| {
+            let params = NameSearchQueryParams {
+                search: search.clone(),
+                cursor,
+                limit: Some(MAX_PAGE_LIMIT),
+                is_linked,
+                org: None,
+            };
+
+            async move { self.search_names(&params).await }
+        })
+        .await
+    }
+
+    /// `GET /v1/names/analytics/{name}`: the (aggregated) analytics of a name.
+    pub async fn name_analytics(&self, name: &str) -> Result<NameAnalyticsResponse> {
+        self.get(&format!("names/analytics/{}", name)).await
+    }
+
+    /// `GET /v1/resolution/{name}`: the package address a (versioned) name resolves to.
+    pub async fn resolve(&self, name: &str) -> Result<ResolutionResponse> {
+        self.get(&format!("resolution/{}", name)).await
+    }
+
+    /// `POST /v1/resolution/bulk`: the package addresses of every name.
+    /// Names that cannot be resolved are omitted.
+    pub async fn bulk_resolve(
+        &self,
+        names: &[impl AsRef<str>],
+    ) -> Result<HashMap<String, ResolutionResponse>> {
+        let responses: Vec<BulkResolutionResponse> = self
+            .post_in_batches("resolution/bulk", names, |names| BulkResolutionRequest {
+                names: to_strings(names),
+            })
+            .await?;
+
+        Ok(merge(responses.into_iter().map(|r| r.resolution)))
+    }
+
+    /// `GET /v1/reverse-resolution/{package_id}`: the name of a package address.
+    pub async fn reverse_resolve(&self, package_id: &Address) -> Result<ReverseResolutionResponse> {
+        self.get(&format!("reverse-resolution/{}", package_id))
+            .await
+    }
+
+    /// `POST /v1/reverse-resolution/bulk`: the names of every package address.
+    /// Addresses without a name are omitted.
+    pub async fn bulk_reverse_resolve(
+        &self,
+        package_ids: &[Address],
+    ) -> Result<HashMap<Address, ReverseResolutionResponse>> {
+        let responses: Vec<BulkReverseResolutionResponse> = self
+            .post_in_batches("reverse-resolution/bulk", package_ids, |package_ids| {
+                BulkReverseResolutionRequest {
+                    package_ids: package_ids.to_vec(),
+                }
+            })
+            .await?;
+
+        Ok(merge(responses.into_iter().map(|r| r.resolution)))
+    }
+
+    /// `GET /v1/type-resolution/{type}`: the canonical type tag of a type with MVR names
+    /// (e.g. `@org/app::module::Type<u64>`).
+    pub async fn resolve_type(&self, type_name: &str) -> Result<TypeResolutionResponse> {
+        self.get(&format!("type-resolution/{}", type_name)).await
+    }
+
+    /// `POST /v1/type-resolution/bulk`: the canonical type tags of every type.
+    pub async fn bulk_resolve_types(
+        &self,
+        types: &[impl AsRef<str>],
+    ) -> Result<HashMap<String, TypeResolutionResponse>> {
+        self.bulk_types("type-resolution/bulk", types).await
+    }
+
+    /// `GET /v1/struct-definition/{type}`: the struct tag of a struct with MVR names, using the
+    /// package's defining ID (e.g. `@org/app::module::Struct`, without type parameters).
+    pub async fn struct_definition(&self, type_name: &str) -> Result<TypeResolutionResponse> {
+        self.get(&format!("struct-definition/{}", type_name)).await
+    }
+
+    /// `POST /v1/struct-definition/bulk`: the struct tags of every struct.
+    pub async fn bulk_struct_definitions(
+        &self,
+        types: &[impl AsRef<str>],
+    ) -> Result<HashMap<String, TypeResolutionResponse>> {
+        self.bulk_types("struct-definition/bulk", types).await
+    }
+
+    /// `GET /v1/package-address/{package_address}/dependencies`: the direct dependencies of a package.
+    pub async fn package_dependencies(
+        &self,
+        package_address: &Address,
+    ) -> Result<PackageDependencies> {
+        self.get(&format!("package-address/{}/dependencies", package_address))
+            .await
+    }
+
+    /// `GET /v1/package-address/{package_address}/dependents`: a page of the dependents of a
+    /// package, ordered by their total calls.
+    pub async fn package_dependents(
+        &self,
+        package_address: &Address,
+        params: &DependentsQueryParams,
+    ) -> Result<PaginatedResponse<PackageDependent>> {
+        self.get_with_query(
+            &format!("package-address/{}/dependents", package_address),
+            params,
+        )
+        .await
+    }
+
+    /// Every dependent of a package, following the cursors of [`Self::package_dependents`].
+    pub async fn all_package_dependents(
+        &self,
+        package_address: &Address,
+    ) -> Result<Vec<PackageDependent>> {
+        collect_pages(|cursor| {
+            let params = DependentsQueryParams {
+                cursor,
+                limit: Some(MAX_PAGE_LIMIT),
+            };
+
+            async move { self.package_dependents(package_address, &params).await }
+        })
+        .await
+    }
+
+    /// `GET /v1/package-address/{package_address}/analytics`: the daily calls to a package.
+    pub async fn package_analytics(
+        &self,
+        package_address: &Address,
+    ) -> Result<PackageAnalyticsResponse> {
+        self.get(&format!("package-address/{}/analytics", package_address))
+            .await
+    }
+
+    /// `GET /v1/package-address/{package_address}/modules`: the on-chain bytecode of every module
+    /// of a package.
+    pub async fn package_modules(&self, package_address: &Address) -> Result<PackageModules> {
+        self.get(&format!("package-address/{}/modules", package_address))
+            .await
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/v1/{}", self.base_url, path)
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        Ok(send_with_retries(&self.http, request, self.max_retries).await?)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = self.url(path);
+        let response = self.send(self.http.get(&url)).await?;
+
+        parse_response(&url, response).await
+    }
+
+    async fn get_with_query<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &impl Serialize,
+    ) -> Result<T> {
+        let url = self.url(path);
+        let response = self.send(self.http.get(&url).query(query)).await?;
+
+        parse_response(&url, response).await
+    }
+
+    async fn bulk_types(
+        &self,
+        path: &str,
+        types: &[impl AsRef<str>],
+    ) -> Result<HashMap<String, TypeResolutionResponse>> {
+        let responses: Vec<BulkTypesResponse> = self
+            .post_in_batches(path, types, |types| BulkTypesRequest {
+                types: to_strings(types),
+            })
+            .await?;
+
+        Ok(merge(responses.into_iter().map(|r| r.resolution)))
+    }
+
+    /// Sends `items` to a bulk endpoint, split in batches of (at most) `batch_size` items.
+    async fn post_in_batches<I, Req, Res>(
+        &self,
+        path: &str,
+        items: &[I],
+        to_request: impl Fn(&[I]) -> Req,
+    ) -> Result<Vec<Res>>
+    where
+        Req: Serialize,
+        Res: DeserializeOwned,
+    {
+        let url = self.url(path);
+
+        let requests = items.chunks(self.batch_size).map(|batch| {
+            let request = self.http.post(&url).json(&to_request(batch));
+
+            async {
+                let response = self.send(request).await?;
+
+                // Bulk endpoints never respond with `404` themselves, so the route does not exist.
+                match parse_response(&url, response).await {
+                    Err(MvrClientError::NotFound(_)) => {
+                        Err(MvrClientError::EndpointNotSupported(url.clone()))
+                    }
+                    result => result,
+                }
+            }
+        });
+
+        try_join_all(requests).await
+    }
+}
+
+/// Collects the items of every page of a paginated endpoint, starting from the first page.
+/// `fetch_page` is called with the cursor of the page to fetch, until a page has no next cursor.
+pub async fn collect_pages<T, F, Fut>(mut fetch_page: F) -> Result<Vec<T>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<PaginatedResponse<T>>>,
+{
+    let mut items = vec![];
+    let mut cursor = None;
+
+    loop {
+        let page = fetch_page(cursor).await?;
+        items.extend(page.data);
+
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => return Ok(items),
+        }
+    }
+}
+
+fn to_strings(items: &[impl AsRef<str>]) -> Vec<String> {
+    items.iter().map(|item| item.as_ref().to_string()).collect()
+}
+
+fn merge<K: Eq + Hash, V>(maps: impl Iterator<Item = HashMap<K, V>>) -> HashMap<K, V> {
+    maps.flatten().collect()
+}
+
+async fn parse_response<T: DeserializeOwned>(url: &str, response: Response) -> Result<T> {
+    error_for_status(url, response)
+        .await?
+        .json::<T>()
+        .await
+        .map_err(|e| {
+            if e.is_decode() {
+                MvrClientError::Decode {
+                    url: url.to_string(),
+                    message: e.to_string(),
+                }
+            } else {
+                MvrClientError::Request(e)
+            }
+        })
+}
+
+/// Turns error responses into a [`MvrClientError`], using the message of the API when available.
+async fn error_for_status(url: &str, response: Response) -> Result<Response> {
+    let status = response.status();
+
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<ErrorResponse>(&body)
+        .map(|error| error.message)
+        .unwrap_or(body);
+
+    Err(match status {
+        StatusCode::NOT_FOUND if message.is_empty() => MvrClientError::NotFound(url.to_string()),
+        StatusCode::NOT_FOUND => MvrClientError::NotFound(message),
+        StatusCode::METHOD_NOT_ALLOWED => MvrClientError::EndpointNotSupported(url.to_string()),
+        status => MvrClientError::Api {
+            status: status.as_u16(),
+            message,
+        },
+    })
+}
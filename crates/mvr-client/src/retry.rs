@@ -0,0 +1,72 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use reqwest::{header::RETRY_AFTER, Client, Method, RequestBuilder, Response, StatusCode};
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Sends a request with `client`, retrying (up to `max_retries` times) with exponential backoff:
+/// - `GET` requests are retried on connection errors, timeouts and server errors.
+/// - Any request is retried when the API responds with `429` or `503`, honoring `Retry-After`.
+pub async fn send_with_retries(
+    client: &Client,
+    request: RequestBuilder,
+    max_retries: u32,
+) -> Result<Response, reqwest::Error> {
+    let request = request.build()?;
+    let idempotent = request.method() == Method::GET;
+
+    let mut attempt = 0;
+
+    loop {
+        let Some(next_request) = request.try_clone() else {
+            // Requests with streaming bodies cannot be retried.
+            return client.execute(request).await;
+        };
+
+        let result = client.execute(next_request).await;
+
+        let retry_after = match &result {
+            Ok(response) if is_rate_limited(response.status()) => {
+                Some(parse_retry_after(response).unwrap_or(backoff(attempt)))
+            }
+            Ok(response) if idempotent && response.status().is_server_error() => {
+                Some(backoff(attempt))
+            }
+            Err(e) if idempotent && (e.is_connect() || e.is_timeout()) => Some(backoff(attempt)),
+            _ => None,
+        };
+
+        match retry_after {
+            Some(delay) if attempt < max_retries => {
+                attempt += 1;
+                tokio::time::sleep(delay.min(MAX_BACKOFF)).await;
+            }
+            _ => return result,
+        }
+    }
+}
+
+fn is_rate_limited(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// Parses a `Retry-After` header, when it is expressed in seconds.
+fn parse_retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF.saturating_mul(2u32.saturating_pow(attempt))
+}
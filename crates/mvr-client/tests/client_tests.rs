@@ -0,0 +1,272 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use mvr_client::{MvrClient, MvrClientError};
+use mvr_types::api::{BulkResolutionRequest, NameSearchQueryParams, MAX_BATCH_SIZE};
+use serde_json::{json, Value};
+use sui_sdk_types::Address;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    headers: String,
+    body: String,
+}
+
+/// Starts a server that answers every request with `handler` (a status and a JSON body),
+/// and records the requests it received.
+async fn serve(
+    handler: impl Fn(&Request) -> (u16, Value) + Send + Sync + 'static,
+) -> (MvrClient, Arc<Mutex<Vec<Request>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(vec![]));
+    let received = requests.clone();
+    let handler = Arc::new(handler);
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let received = received.clone();
+            let handler = handler.clone();
+
+            tokio::spawn(async move {
+                let request = read_request(&mut socket).await;
+                let (status, body) = handler(&request);
+                received.lock().unwrap().push(request);
+
+                let body = body.to_string();
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            });
+        }
+    });
+
+    (MvrClient::new(url).with_max_retries(0), requests)
+}
+
+async fn read_request(socket: &mut TcpStream) -> Request {
+    let mut buf = vec![];
+    let mut chunk = vec![0; 4096];
+
+    let header_end = loop {
+        let n = socket.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+
+    let headers = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let content_length = headers
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().unwrap())
+        })
+        .unwrap_or_default();
+
+    while buf.len() < header_end + content_length {
+        let n = socket.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let mut request_line = headers.split_whitespace();
+
+    Request {
+        method: request_line.next().unwrap().to_string(),
+        path: request_line.next().unwrap().to_string(),
+        body: String::from_utf8_lossy(&buf[header_end..]).to_string(),
+        headers,
+    }
+}
+
+fn package_json(name: &str, address: &str) -> Value {
+    json!({
+        "name": name,
+        "metadata": {},
+        "package_info": null,
+        "git_info": {
+            "repository_url": "https://github.com/MystenLabs/mvr",
+            "path": "packages/demo",
+            "tag": "main"
+        },
+        "version": 1,
+        "package_address": address,
+    })
+}
+
+#[tokio::test]
+async fn test_package_by_name() {
+    let (client, requests) = serve(|request| match request.path.as_str() {
+        "/v1/names/@mvr/demo" => (200, package_json("@mvr/demo", "0x1")),
+        _ => (404, json!({ "message": "Name not found" })),
+    })
+    .await;
+
+    let package = client.package_by_name("@mvr/demo").await.unwrap();
+    assert_eq!(package.package_by_name_data.name, "@mvr/demo");
+    assert_eq!(package.package_address, "0x1".parse::<Address>().unwrap());
+    assert_eq!(package.package_by_name_data.git_info.unwrap().tag, "main");
+
+    let err = client.package_by_name("@mvr/unknown").await.unwrap_err();
+    assert!(matches!(&err, MvrClientError::NotFound(message) if message == "Name not found"));
+    assert_eq!(err.status(), Some(404));
+
+    assert!(requests.lock().unwrap().iter().all(|r| r.method == "GET"));
+}
+
+#[tokio::test]
+async fn test_bulk_requests_are_chunked() {
+    let (client, requests) = serve(|request| {
+        let body: BulkResolutionRequest = serde_json::from_str(&request.body).unwrap();
+        assert!(body.names.len() <= MAX_BATCH_SIZE);
+
+        let resolution: HashMap<_, _> = body
+            .names
+            .into_iter()
+            .map(|name| (name, json!({ "package_id": "0x1" })))
+            .collect();
+        (200, json!({ "resolution": resolution }))
+    })
+    .await;
+
+    let names: Vec<String> = (0..=MAX_BATCH_SIZE)
+        .map(|i| format!("@mvr/app-{i}"))
+        .collect();
+    let resolution = client.bulk_resolve(&names).await.unwrap();
+
+    assert_eq!(resolution.len(), names.len());
+    assert!(names.iter().all(|name| resolution.contains_key(name)));
+
+    {
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|r| r.method == "POST" && r.path == "/v1/resolution/bulk"));
+    }
+
+    // A smaller batch size splits the names in more requests.
+    let resolution = client
+        .clone()
+        .with_batch_size(10)
+        .bulk_resolve(&names[..25])
+        .await
+        .unwrap();
+    assert_eq!(resolution.len(), 25);
+    assert_eq!(requests.lock().unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn test_empty_bulk_requests_are_not_sent() {
+    let (client, requests) = serve(|_| (500, json!({}))).await;
+
+    let names: &[String] = &[];
+    assert!(client.bulk_package_by_name(names).await.unwrap().is_empty());
+    assert!(requests.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_pagination_follows_cursors() {
+    let (client, requests) = serve(|request| {
+        let cursor = request.path.split(['?', '&']).find_map(|p| p.strip_prefix("cursor="));
+        let page = match cursor {
+            None => json!({ "data": [{ "name": "@mvr/a", "metadata": {}, "mainnet_package_info_id": null, "testnet_package_info_id": null }], "next_cursor": "page-2", "limit": 1, "total": null }),
+            Some("page-2") => json!({ "data": [{ "name": "@mvr/b", "metadata": {}, "mainnet_package_info_id": "0x2", "testnet_package_info_id": null }], "next_cursor": null, "limit": 1, "total": null }),
+            Some(cursor) => panic!("unexpected cursor {cursor}"),
+        };
+        (200, page)
+    })
+    .await;
+
+    let first_page = client
+        .search_names(&NameSearchQueryParams {
+            search: Some("mvr".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(first_page.next_cursor.as_deref(), Some("page-2"));
+
+    let names = client
+        .all_names(Some("mvr".to_string()), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        names.iter().map(|n| n.name.as_str()).collect::<Vec<_>>(),
+        vec!["@mvr/a", "@mvr/b"]
+    );
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].path.starts_with("/v1/names?search=mvr"));
+    assert!(requests[2].path.contains("cursor=page-2"));
+}
+
+#[tokio::test]
+async fn test_error_mapping() {
+    let (client, _) = serve(|request| match request.path.as_str() {
+        "/v1/names/bulk" => (404, json!({})),
+        "/v1/resolution/bulk" => (400, json!({ "message": "Too many names" })),
+        path if path.ends_with("/dependencies") => (200, json!({ "dependencies": "0x2" })),
+        _ => (405, json!({})),
+    })
+    .await;
+
+    let names = vec!["@mvr/demo".to_string()];
+
+    let err = client.bulk_package_by_name(&names).await.unwrap_err();
+    assert!(
+        matches!(err, MvrClientError::EndpointNotSupported(url) if url.ends_with("/v1/names/bulk"))
+    );
+
+    let err = client.bulk_resolve(&names).await.unwrap_err();
+    assert!(
+        matches!(&err, MvrClientError::Api { status: 400, message } if message == "Too many names")
+    );
+
+    let address = "0x1".parse().unwrap();
+    let err = client.package_dependencies(&address).await.unwrap_err();
+    assert!(matches!(err, MvrClientError::Decode { .. }));
+
+    let err = client.package_modules(&address).await.unwrap_err();
+    assert!(matches!(err, MvrClientError::EndpointNotSupported(_)));
+}
+
+#[tokio::test]
+async fn test_source_header() {
+    let http = reqwest::Client::builder()
+        .default_headers(
+            [(
+                mvr_client::MVR_SOURCE_HEADER,
+                "mvr-client-tests".parse().unwrap(),
+            )]
+            .into_iter()
+            .map(|(name, value)| (name.parse().unwrap(), value))
+            .collect(),
+        )
+        .build()
+        .unwrap();
+
+    let (client, requests) = serve(|_| (200, json!({}))).await;
+    client.with_http_client(http).health().await.unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].path, "/health");
+    assert!(requests[0]
+        .headers
+        .to_lowercase()
+        .contains("mvr-source: mvr-client-tests"));
+}
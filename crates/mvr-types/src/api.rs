@@ -0,0 +1,279 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The request and response types of the MVR API (`/v1/...`), shared by the server and its clients.
+//!
+//! With the `diesel` feature, the types that are read straight from the database also derive
+//! the diesel traits the server needs.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sui_sdk_types::Address;
+
+/// The maximum number of items the bulk endpoints accept in a single request.
+pub const MAX_BATCH_SIZE: usize = 50;
+/// The number of items of a page, when no limit is requested.
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+/// The maximum number of items of a page.
+pub const MAX_PAGE_LIMIT: u32 = 50;
+
+/// The body of every error response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ErrorResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaginatedResponse<T> {
+    pub data: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub limit: u32,
+    /// Total is optional, and only returned when the count is also retrievable.
+    pub total: Option<i64>,
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn new(
+        mut data: Vec<T>,
+        next_cursor: Option<String>,
+        limit: u32,
+        total: Option<i64>,
+    ) -> Self {
+        // if the result has more items than the limit, we pop the last item, as it
+        // is only used to determine if there is a next page
+        if data.len() > limit as usize {
+            data.pop();
+        }
+
+        Self {
+            data,
+            next_cursor,
+            limit,
+            total,
+        }
+    }
+}
+
+// `/v1/names`
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PackageByNameBaseData {
+    pub name: String,
+    pub metadata: Value,
+    pub package_info: Option<PackageInfoResponse>,
+    pub git_info: Option<GitInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PackageInfoResponse {
+    pub id: String,
+    pub git_table_id: String,
+    pub default_name: Option<String>,
+    pub metadata: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GitInfo {
+    pub repository_url: String,
+    pub path: String,
+    pub tag: String,
+}
+
+/// The response of `GET /v1/names/{name}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PackageByNameResponse {
+    #[serde(flatten)]
+    pub package_by_name_data: PackageByNameBaseData,
+    pub version: i64,
+    pub package_address: Address,
+}
+
+/// The request of `POST /v1/names/bulk`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkNamesRequest {
+    pub names: Vec<String>,
+}
+
+/// The response of `POST /v1/names/bulk`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkNamesResponse {
+    /// The package data of every requested name. Names that are malformed or cannot be found map
+    /// to `null`.
+    pub names: HashMap<String, Option<PackageByNameResponse>>,
+}
+
+/// An item of `GET /v1/names` (paginated).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "diesel", derive(diesel::QueryableByName))]
+pub struct NameSearchResponse {
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
+    pub name: String,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Jsonb))]
+    pub metadata: Value,
+    #[cfg_attr(
+        feature = "diesel",
+        diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)
+    )]
+    pub mainnet_package_info_id: Option<String>,
+    #[cfg_attr(
+        feature = "diesel",
+        diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)
+    )]
+    pub testnet_package_info_id: Option<String>,
+}
+
+/// The query parameters of `GET /v1/names`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct NameSearchQueryParams {
+    pub search: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+    pub is_linked: Option<bool>,
+    /// Only return names of this organization (e.g. `@mvr` or `mvr.sui`).
+    pub org: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnalyticsValue {
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+    pub direct: i64,
+    pub propagated: i64,
+    pub total: i64,
+}
+
+/// The response of `GET /v1/names/analytics/{name}`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameAnalyticsResponse {
+    pub analytics: Vec<AnalyticsValue>,
+    pub total_dependents: i64,
+}
+
+// `/v1/resolution`
+
+/// The request of `POST /v1/resolution/bulk`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkResolutionRequest {
+    pub names: Vec<String>,
+}
+
+/// The response of `GET /v1/resolution/{name}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionResponse {
+    pub package_id: Option<Address>,
+}
+
+/// The response of `POST /v1/resolution/bulk`. Names that cannot be resolved are omitted.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkResolutionResponse {
+    pub resolution: HashMap<String, ResolutionResponse>,
+}
+
+// `/v1/reverse-resolution`
+
+/// The request of `POST /v1/reverse-resolution/bulk`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkReverseResolutionRequest {
+    pub package_ids: Vec<Address>,
+}
+
+/// The response of `GET /v1/reverse-resolution/{package_id}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReverseResolutionResponse {
+    pub name: Option<String>,
+}
+
+/// The response of `POST /v1/reverse-resolution/bulk`. Addresses without a name are omitted.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkReverseResolutionResponse {
+    pub resolution: HashMap<Address, ReverseResolutionResponse>,
+}
+
+// `/v1/type-resolution` and `/v1/struct-definition`
+
+/// The request of `POST /v1/type-resolution/bulk` and `POST /v1/struct-definition/bulk`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkTypesRequest {
+    pub types: Vec<String>,
+}
+
+/// The response of `GET /v1/type-resolution/{type}` and `GET /v1/struct-definition/{type}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TypeResolutionResponse {
+    pub type_tag: Option<String>,
+}
+
+/// The response of `POST /v1/type-resolution/bulk` and `POST /v1/struct-definition/bulk`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BulkTypesResponse {
+    pub resolution: HashMap<String, TypeResolutionResponse>,
+}
+
+// `/v1/package-address`
+
+/// The response of `GET /v1/package-address/{package_address}/dependencies`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageDependencies {
+    pub dependencies: Vec<Address>,
+}
+
+/// The query parameters of `GET /v1/package-address/{package_address}/dependents`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DependentsQueryParams {
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// An item of `GET /v1/package-address/{package_address}/dependents` (paginated).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "diesel", derive(diesel::QueryableByName))]
+pub struct PackageDependent {
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
+    pub package_id: String,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub aggregated_total_calls: i64,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub aggregated_direct_calls: i64,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub aggregated_propagated_calls: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "diesel", derive(diesel::Queryable))]
+pub struct PackageAnalytics {
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::VarChar))]
+    pub package_id: String,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Date))]
+    pub call_date: NaiveDate,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub direct_calls: i64,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub propagated_calls: i64,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub total_calls: i64,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub aggregated_direct_calls: i64,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub aggregated_propagated_calls: i64,
+    #[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+    pub aggregated_total_calls: i64,
+}
+
+/// The response of `GET /v1/package-address/{package_address}/analytics`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageAnalyticsResponse {
+    pub analytics: Vec<PackageAnalytics>,
+}
+
+/// The response of `GET /v1/package-address/{package_address}/modules`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PackageModules {
+    pub package_id: Address,
+    pub original_id: Address,
+    pub version: u64,
+    /// The serialized bytecode of each module (base64 encoded), keyed by the module name.
+    pub modules: BTreeMap<String, String>,
+}
@@ -1,3 +1,4 @@
+pub mod api;
 pub mod errors;
 pub mod name;
 pub mod name_service;